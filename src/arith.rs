@@ -0,0 +1,136 @@
+use std::fmt;
+
+/// Integer arithmetic that honours the crate's overflow policy.
+///
+/// With the `checked-arithmetic` feature every operation is checked and an overflow is reported
+/// as an [`OverflowError`] naming the day and operation. Without it the native operators are used,
+/// which wrap silently in release builds.
+pub trait Arith: Copy + fmt::Display + Sized {
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+    fn checked_pow(self, exp: u32) -> Option<Self>;
+    fn native_add(self, rhs: Self) -> Self;
+    fn native_sub(self, rhs: Self) -> Self;
+    fn native_mul(self, rhs: Self) -> Self;
+    fn native_pow(self, exp: u32) -> Self;
+}
+
+macro_rules! impl_arith {
+    ($($t:ty),*) => {
+        $(
+            impl Arith for $t {
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_add(self, rhs)
+                }
+                fn checked_sub(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_sub(self, rhs)
+                }
+                fn checked_mul(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_mul(self, rhs)
+                }
+                fn checked_pow(self, exp: u32) -> Option<Self> {
+                    <$t>::checked_pow(self, exp)
+                }
+                fn native_add(self, rhs: Self) -> Self {
+                    self + rhs
+                }
+                fn native_sub(self, rhs: Self) -> Self {
+                    self - rhs
+                }
+                fn native_mul(self, rhs: Self) -> Self {
+                    self * rhs
+                }
+                fn native_pow(self, exp: u32) -> Self {
+                    self.pow(exp)
+                }
+            }
+        )*
+    };
+}
+
+impl_arith!(i64, u64, isize, usize);
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OverflowError {
+    pub day: u8,
+    pub operation: &'static str,
+    pub lhs: String,
+    pub rhs: String,
+}
+
+impl fmt::Display for OverflowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Day {:0>2}: arithmetic overflow in {} ({}, {})",
+            self.day, self.operation, self.lhs, self.rhs
+        )
+    }
+}
+
+impl std::error::Error for OverflowError {}
+
+#[inline]
+fn apply<T: Arith, R: fmt::Display>(
+    day: u8,
+    operation: &'static str,
+    lhs: T,
+    rhs: R,
+    checked: impl FnOnce() -> Option<T>,
+    native: impl FnOnce() -> T,
+) -> Result<T, OverflowError> {
+    if !cfg!(feature = "checked-arithmetic") {
+        return Ok(native());
+    }
+    checked().ok_or_else(|| OverflowError {
+        day,
+        operation,
+        lhs: lhs.to_string(),
+        rhs: rhs.to_string(),
+    })
+}
+
+pub fn add<T: Arith>(day: u8, lhs: T, rhs: T) -> Result<T, OverflowError> {
+    apply(
+        day,
+        "addition",
+        lhs,
+        rhs,
+        || lhs.checked_add(rhs),
+        || lhs.native_add(rhs),
+    )
+}
+
+pub fn sub<T: Arith>(day: u8, lhs: T, rhs: T) -> Result<T, OverflowError> {
+    apply(
+        day,
+        "subtraction",
+        lhs,
+        rhs,
+        || lhs.checked_sub(rhs),
+        || lhs.native_sub(rhs),
+    )
+}
+
+pub fn mul<T: Arith>(day: u8, lhs: T, rhs: T) -> Result<T, OverflowError> {
+    apply(
+        day,
+        "multiplication",
+        lhs,
+        rhs,
+        || lhs.checked_mul(rhs),
+        || lhs.native_mul(rhs),
+    )
+}
+
+pub fn pow<T: Arith>(day: u8, base: T, exp: u32) -> Result<T, OverflowError> {
+    apply(
+        day,
+        "exponentiation",
+        base,
+        exp,
+        || base.checked_pow(exp),
+        || base.native_pow(exp),
+    )
+}
@@ -1,8 +1,5 @@
 use core::fmt;
-use std::{
-    fmt::write,
-    ops::{Add, AddAssign, Mul, MulAssign, Sub, SubAssign},
-};
+use std::ops::{Add, AddAssign, Mul, MulAssign, Sub, SubAssign};
 
 pub const UP: Coord = Coord(-1, 0);
 pub const RIGHT: Coord = Coord(0, 1);
@@ -0,0 +1,71 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::coord::{Coord, DIAGONAL_DIRECTIONS, ORTHOGONAL_DIRECTIONS};
+
+/// Which neighbouring cells count as touching.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Connectivity {
+    /// Cells sharing an edge
+    Four,
+    /// Cells sharing an edge or a corner
+    Eight,
+}
+
+impl Connectivity {
+    pub fn directions(&self) -> &'static [Coord] {
+        match self {
+            Connectivity::Four => &ORTHOGONAL_DIRECTIONS,
+            Connectivity::Eight => &DIAGONAL_DIRECTIONS,
+        }
+    }
+}
+
+/// The result of labeling a grid's connected components.
+#[derive(Clone, Debug, Default)]
+pub struct Components {
+    /// Cells of each component, in reading order. Components are ordered by their first cell.
+    pub regions: Vec<Vec<Coord>>,
+    /// Index into `regions` of every labeled cell
+    pub labels: HashMap<Coord, usize>,
+}
+
+/// Groups the grid's cells into connected components, where touching cells belong to the same
+/// component if `same` holds for their values. The grid may have any shape, e.g. rows of
+/// different lengths; cells missing from it separate components.
+pub fn label_components<T, F>(
+    grid: &HashMap<Coord, T>,
+    connectivity: Connectivity,
+    same: F,
+) -> Components
+where
+    F: Fn(&T, &T) -> bool,
+{
+    let mut cells: Vec<Coord> = grid.keys().copied().collect();
+    cells.sort_by_key(|c| (c.0, c.1));
+    let mut components = Components::default();
+    for start in cells {
+        if components.labels.contains_key(&start) {
+            continue;
+        }
+        let label = components.regions.len();
+        let mut region = vec![];
+        let mut queue: VecDeque<Coord> = VecDeque::from([start]);
+        components.labels.insert(start, label);
+        while let Some(cell) = queue.pop_front() {
+            region.push(cell);
+            for &direction in connectivity.directions() {
+                let neighbour = cell + direction;
+                let Some(value) = grid.get(&neighbour) else {
+                    continue;
+                };
+                if !components.labels.contains_key(&neighbour) && same(&grid[&cell], value) {
+                    components.labels.insert(neighbour, label);
+                    queue.push_back(neighbour);
+                }
+            }
+        }
+        region.sort_by_key(|c| (c.0, c.1));
+        components.regions.push(region);
+    }
+    components
+}
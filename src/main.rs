@@ -1,88 +1,96 @@
+pub mod arith;
 pub mod coord;
-mod day01;
-mod day02;
-mod day03;
-mod day04;
-mod day05;
-mod day06;
-mod day07;
-mod day08;
-mod day09;
-mod day10;
-mod day11;
-mod day12;
-mod day13;
-mod day14;
-mod day15;
-mod day16;
+pub mod grid;
 pub mod matrix;
+pub mod options;
+mod scaffold;
+mod y2024;
 
 use std::{env, ffi::OsString, path::Path, process::exit};
 
 use anyhow::Result;
-use day01::day01;
-use day02::day02;
-use day03::day03;
-use day04::day04;
-use day05::day05;
-use day06::day06;
-use day07::day07;
-use day08::day08;
-use day09::day09;
-use day10::day10;
-use day11::day11;
-use day12::day12;
-use day13::day13;
-use day14::day14;
-use day15::day15;
-use day16::day16;
+use options::Options;
+
+/// Solves one day's puzzle, returning the answers to both parts.
+pub type Solver = fn(&Path, &Options) -> Result<(String, String)>;
+
+/// Year assumed when only a day is given.
+const DEFAULT_YEAR: u16 = 2024;
+
+/// Looks up a day's solver in the given year's registry.
+fn solver(year: u16, day: u8) -> Option<Solver> {
+    match year {
+        2024 => y2024::solver(day),
+        _ => None,
+    }
+}
+
+fn parse_number<T: std::str::FromStr>(arg: &OsString, what: &str) -> T {
+    arg.to_str()
+        .unwrap_or_else(|| panic!("Error parsing {} token.", what))
+        .parse::<T>()
+        .unwrap_or_else(|_| panic!("Error converting {} token to number.", what))
+}
 
 fn main() -> Result<()> {
     let args: Vec<_> = env::args_os().skip(1).collect();
-    if args.len() != 1 && args.len() != 2 {
+    if args.is_empty() {
         eprintln!(
-            "Proper usage: 'cargo run {{day}} {{path_to_input}}' ({} arguments detected)",
-            args.len()
+            "Proper usage: 'cargo run [{{year}}] {{day}} [{{path_to_input}}] [--option=value ...]'\n\
+             To add a day:  'cargo run new [{{year}}] {{day}}'"
         );
         exit(-1);
     }
-    let day = args
-        .first()
-        .unwrap()
-        .to_owned()
-        .into_string()
-        .expect("Error parsing day token.")
-        .parse::<u8>()
-        .expect("Error converting day token to number.");
+    if args[0] == "new" {
+        let (year, day) = match &args[1..] {
+            [day] => (DEFAULT_YEAR, parse_number::<u8>(day, "day")),
+            [year, day] => (parse_number(year, "year"), parse_number::<u8>(day, "day")),
+            _ => {
+                eprintln!("Proper usage: 'cargo run new [{{year}}] {{day}}'");
+                exit(-1);
+            }
+        };
+        if day == 0 || day > 25 {
+            eprintln!("Provided day is not between 1-25.");
+            exit(-1);
+        }
+        for path in scaffold::new_day(year, day)? {
+            println!("Wrote {}", path.display());
+        }
+        return Ok(());
+    }
+    // A leading number above 25 is a year; otherwise it is a day of the default year.
+    let first: u16 = parse_number(&args[0], "day");
+    let (year, day, rest) = match (first, args.get(1)) {
+        (1..=25, _) => (DEFAULT_YEAR, first as u8, &args[1..]),
+        (_, Some(day)) => (first, parse_number::<u8>(day, "day"), &args[2..]),
+        (_, None) => {
+            eprintln!("Missing day after year {}.", first);
+            exit(-1);
+        }
+    };
     if day == 0 || day > 25 {
         eprintln!("Provided day is not between 1-25.");
         exit(-1);
     }
-    let default_input_path = OsString::from(format!("../inputs/{:0>2}.txt", day.to_string()));
-    let input_path = Path::new(args.get(1).unwrap_or(&default_input_path));
-    println!("Day: {:0>2} Input Path: {:?}", day.to_string(), input_path);
-    let (p1, p2) = match day {
-        1 => day01(input_path),
-        2 => day02(input_path),
-        3 => day03(input_path),
-        4 => day04(input_path),
-        5 => day05(input_path),
-        6 => day06(input_path),
-        7 => day07(input_path),
-        8 => day08(input_path),
-        9 => day09(input_path),
-        10 => day10(input_path),
-        11 => day11(input_path),
-        12 => day12(input_path),
-        13 => day13(input_path),
-        14 => day14(input_path),
-        15 => day15(input_path),
-        16 => day16(input_path),
-        _ => {
-            eprintln!("Invalid day: {}", day);
-            exit(-1);
-        }
-    }?;
+    let default_input_path = OsString::from(format!("../inputs/{}/{:0>2}.txt", year, day));
+    let (input_path, option_args) = match rest.first() {
+        Some(arg) if !arg.to_string_lossy().starts_with("--") => (arg, &rest[1..]),
+        _ => (&default_input_path, rest),
+    };
+    let input_path = Path::new(input_path);
+    let options = Options::parse(option_args)?;
+    let Some(solve) = solver(year, day) else {
+        eprintln!("Invalid day: {} {}", year, day);
+        exit(-1);
+    };
+    println!(
+        "Year: {} Day: {:0>2} Input Path: {:?}",
+        year,
+        day.to_string(),
+        input_path
+    );
+    let (p1, p2) = solve(input_path, &options)?;
     println!("p1:\n{}\np2:\n{}", p1, p2);
     Ok(())
 }
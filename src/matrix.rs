@@ -1,3 +1,5 @@
+use crate::arith::{self, OverflowError};
+
 #[derive(Clone, Copy, Debug)]
 pub struct Matrix2x2 {
     pub a: i64,
@@ -14,6 +16,15 @@ impl Matrix2x2 {
     pub fn det(&self) -> i64 {
         self.a * self.d - self.b * self.c
     }
+
+    /// Determinant computed under the crate's overflow policy, see [`crate::arith`].
+    pub fn try_det(&self, day: u8) -> Result<i64, OverflowError> {
+        arith::sub(
+            day,
+            arith::mul(day, self.a, self.d)?,
+            arith::mul(day, self.b, self.c)?,
+        )
+    }
 }
 
 impl std::fmt::Display for Matrix2x2 {
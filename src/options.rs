@@ -0,0 +1,51 @@
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::str::FromStr;
+
+use anyhow::{anyhow, Result};
+
+/// Extra `--key=value` (or bare `--key`) arguments given after the day and input path.
+/// Each day reads the keys it understands and falls back to the puzzle's defaults otherwise.
+#[derive(Clone, Debug, Default)]
+pub struct Options(HashMap<String, String>);
+
+impl Options {
+    pub fn parse(args: &[OsString]) -> Result<Self> {
+        let mut options = HashMap::new();
+        for arg in args {
+            let arg = arg
+                .to_str()
+                .ok_or_else(|| anyhow!("Option {:?} is not valid UTF-8", arg))?;
+            let arg = arg.strip_prefix("--").ok_or_else(|| {
+                anyhow!(
+                    "Expected an option of the form '--key=value', got '{}'",
+                    arg
+                )
+            })?;
+            match arg.split_once('=') {
+                Some((key, value)) => options.insert(key.to_string(), value.to_string()),
+                None => options.insert(arg.to_string(), String::new()),
+            };
+        }
+        Ok(Self(options))
+    }
+
+    /// Returns true if `--key` was given, with or without a value.
+    pub fn flag(&self, key: &str) -> bool {
+        self.0.contains_key(key)
+    }
+
+    pub fn get_str(&self, key: &str) -> Option<&str> {
+        self.0.get(key).map(|v| v.as_str())
+    }
+
+    /// Parses the value of `--key=value`, or returns `default` if the option was not given.
+    pub fn get_or<T: FromStr>(&self, key: &str, default: T) -> Result<T> {
+        match self.0.get(key) {
+            None => Ok(default),
+            Some(value) => value
+                .parse()
+                .map_err(|_| anyhow!("Invalid value '{}' for option '--{}'", value, key)),
+        }
+    }
+}
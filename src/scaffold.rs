@@ -0,0 +1,133 @@
+use std::fs::{create_dir_all, read_to_string, write};
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, bail, Result};
+
+const DAY_TEMPLATE: &str = include_str!("../templates/day.rs");
+const YEAR_TEMPLATE: &str = include_str!("../templates/year.rs");
+
+/// Inserts `new` among the lines for which `key` returns a number, keeping them in order.
+/// If there are none yet, `new` goes right before the line `anchor`, followed by a blank line
+/// when `separate` is set.
+fn insert_line(
+    source: &str,
+    new: &str,
+    key: impl Fn(&str) -> Option<u32>,
+    anchor: &str,
+    separate: bool,
+) -> Result<String> {
+    let mut lines: Vec<&str> = source.lines().collect();
+    let new_key = key(new).unwrap();
+    let peers: Vec<(usize, u32)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, line)| key(line).map(|k| (i, k)))
+        .collect();
+    if peers.iter().any(|&(_, k)| k == new_key) {
+        bail!("'{}' is already registered", new.trim());
+    }
+    match peers.iter().find(|&&(_, k)| k > new_key) {
+        Some(&(i, _)) => lines.insert(i, new),
+        None => match peers.last() {
+            Some(&(i, _)) => lines.insert(i + 1, new),
+            None => {
+                let i = lines
+                    .iter()
+                    .position(|line| line.trim() == anchor)
+                    .ok_or_else(|| anyhow!("Could not find '{}' to register '{}'", anchor, new))?;
+                if separate {
+                    lines.insert(i, "");
+                }
+                lines.insert(i, new);
+            }
+        },
+    }
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Number in a `mod <prefix>NN;` declaration.
+fn mod_key(prefix: &'static str) -> impl Fn(&str) -> Option<u32> {
+    move |line| line.strip_prefix(prefix)?.strip_suffix(';')?.parse().ok()
+}
+
+/// Number in a `use dayNN::dayNN;` import.
+fn use_key(line: &str) -> Option<u32> {
+    line.strip_prefix("use day")?
+        .split_once("::")?
+        .0
+        .parse()
+        .ok()
+}
+
+/// Number matched by a `NN => ...` match arm.
+fn arm_key(line: &str) -> Option<u32> {
+    line.trim().split_once(" => ")?.0.parse().ok()
+}
+
+/// Creates `src/y<year>/day<NN>.rs` from the day template and registers it with the year's
+/// solver registry, creating the year's module first if needed. Returns the files it wrote.
+pub fn new_day(year: u16, day: u8) -> Result<Vec<PathBuf>> {
+    let src = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+    let year_dir = src.join(format!("y{}", year));
+    let year_mod = year_dir.join("mod.rs");
+    let name = format!("day{:0>2}", day);
+    let day_file = year_dir.join(format!("{}.rs", name));
+    if day_file.exists() {
+        bail!("{} already exists", day_file.display());
+    }
+    // Every edit is made in memory first, so nothing is written unless all of them succeed
+    let mut files: Vec<(PathBuf, String)> = vec![];
+    let year_source = match year_mod.exists() {
+        true => read_to_string(&year_mod)?,
+        false => {
+            let main = src.join("main.rs");
+            let source = insert_line(
+                &read_to_string(&main)?,
+                &format!("mod y{};", year),
+                mod_key("mod y"),
+                "",
+                false,
+            )?;
+            let source = insert_line(
+                &source,
+                &format!("        {} => y{}::solver(day),", year, year),
+                arm_key,
+                "_ => None,",
+                false,
+            )?;
+            files.push((main, source));
+            YEAR_TEMPLATE.replace("{{year}}", &year.to_string())
+        }
+    };
+    let year_source = insert_line(
+        &year_source,
+        &format!("mod {};", name),
+        mod_key("mod day"),
+        "use crate::Solver;",
+        true,
+    )?;
+    let year_source = insert_line(
+        &year_source,
+        &format!("use {}::{};", name, name),
+        use_key,
+        "use crate::Solver;",
+        true,
+    )?;
+    let year_source = insert_line(
+        &year_source,
+        &format!("        {} => {},", day, name),
+        arm_key,
+        "_ => return None,",
+        false,
+    )?;
+    files.push((year_mod, year_source));
+    files.push((
+        day_file,
+        DAY_TEMPLATE.replace("{{day}}", &format!("{:0>2}", day)),
+    ));
+    create_dir_all(&year_dir)?;
+    for (path, source) in &files {
+        write(path, source)?;
+    }
+    Ok(files.into_iter().map(|(path, _)| path).collect())
+}
@@ -0,0 +1,189 @@
+use anyhow::{anyhow, bail, Result};
+use itertools::Itertools;
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+
+use crate::options::Options;
+
+/// How often each location ID appears in one list. Keeping counts instead of the IDs themselves
+/// sorts the list as it is read, in memory proportional to the distinct IDs rather than the lines.
+enum Counts {
+    /// Counting sort over IDs known to be at most `counts.len() - 1`.
+    Dense(Vec<u64>),
+    Sparse(BTreeMap<usize, u64>),
+}
+
+impl Counts {
+    fn new(max_value: Option<usize>) -> Self {
+        match max_value {
+            Some(max) => Counts::Dense(vec![0; max + 1]),
+            None => Counts::Sparse(BTreeMap::new()),
+        }
+    }
+
+    fn add(&mut self, value: usize) -> Result<()> {
+        match self {
+            Counts::Dense(counts) => match counts.get_mut(value) {
+                Some(count) => *count += 1,
+                None => bail!(
+                    "Location ID {} exceeds --max-value={}",
+                    value,
+                    counts.len() - 1
+                ),
+            },
+            Counts::Sparse(counts) => *counts.entry(value).or_default() += 1,
+        }
+        Ok(())
+    }
+
+    fn get(&self, value: usize) -> u64 {
+        match self {
+            Counts::Dense(counts) => counts.get(value).copied().unwrap_or(0),
+            Counts::Sparse(counts) => counts.get(&value).copied().unwrap_or(0),
+        }
+    }
+
+    /// Each distinct ID with its count, in increasing order of ID.
+    fn runs(&self) -> Box<dyn Iterator<Item = (usize, u64)> + '_> {
+        match self {
+            Counts::Dense(counts) => Box::new(
+                counts
+                    .iter()
+                    .enumerate()
+                    .filter(|&(_, &count)| count > 0)
+                    .map(|(value, &count)| (value, count)),
+            ),
+            Counts::Sparse(counts) => {
+                Box::new(counts.iter().map(|(&value, &count)| (value, count)))
+            }
+        }
+    }
+}
+
+/// Statistics over the pairs formed by matching the smallest left ID with the smallest right ID,
+/// the second smallest with the second smallest, and so on.
+pub struct ListStats {
+    pub pairs: u64,
+    pub total_distance: u64,
+    pub similarity: u64,
+    /// Number of pairs whose IDs differ.
+    pub mismatched: u64,
+    /// Number of pairs at each distance.
+    pub distances: BTreeMap<u64, u64>,
+}
+
+impl ListStats {
+    /// Median distance between paired IDs, as twice its value so even counts stay exact.
+    fn double_median(&self) -> Option<u64> {
+        if self.pairs == 0 {
+            return None;
+        }
+        let nth = |n: u64| {
+            let mut seen = 0;
+            self.distances
+                .iter()
+                .find(|&(_, &count)| {
+                    seen += count;
+                    seen > n
+                })
+                .map(|(&distance, _)| distance)
+                .unwrap()
+        };
+        Some(nth((self.pairs - 1) / 2) + nth(self.pairs / 2))
+    }
+}
+
+/// Reads lines of two whitespace-separated location IDs and compares the two lists.
+/// With `max_value`, IDs are counting-sorted into a fixed-size table.
+pub fn list_stats<R: BufRead>(reader: R, max_value: Option<usize>) -> Result<ListStats> {
+    let mut left = Counts::new(max_value);
+    let mut right = Counts::new(max_value);
+    for (n, line) in reader.lines().enumerate() {
+        let line = line?;
+        let Some((l, r)) = line.split_whitespace().collect_tuple() else {
+            if line.trim().is_empty() {
+                continue;
+            }
+            bail!("Line {}: expected two location IDs, got '{}'", n + 1, line);
+        };
+        let parse = |t: &str| {
+            t.parse::<usize>()
+                .map_err(|_| anyhow!("Line {}: invalid location ID '{}'", n + 1, t))
+        };
+        left.add(parse(l)?)?;
+        right.add(parse(r)?)?;
+    }
+    let mut stats = ListStats {
+        pairs: 0,
+        total_distance: 0,
+        similarity: 0,
+        mismatched: 0,
+        distances: BTreeMap::new(),
+    };
+    // Walk both sorted lists together, a run of equal pairs at a time
+    let mut left_runs = left.runs();
+    let mut right_runs = right.runs();
+    let mut left_run = left_runs.next();
+    let mut right_run = right_runs.next();
+    while let (Some((l, l_count)), Some((r, r_count))) = (left_run, right_run) {
+        let count = l_count.min(r_count);
+        let distance = l.abs_diff(r) as u64;
+        stats.pairs += count;
+        stats.total_distance += distance * count;
+        *stats.distances.entry(distance).or_default() += count;
+        if distance > 0 {
+            stats.mismatched += count;
+        }
+        left_run = match l_count - count {
+            0 => left_runs.next(),
+            remaining => Some((l, remaining)),
+        };
+        right_run = match r_count - count {
+            0 => right_runs.next(),
+            remaining => Some((r, remaining)),
+        };
+    }
+    if left_run.is_some() || right_run.is_some() {
+        bail!("The two lists have different lengths");
+    }
+    stats.similarity = left
+        .runs()
+        .map(|(value, count)| value as u64 * count * right.get(value))
+        .sum();
+    Ok(stats)
+}
+
+pub fn day01(input_path: &Path, options: &Options) -> Result<(String, String)> {
+    let max_value: Option<usize> = match options.get_str("max-value") {
+        Some(_) => Some(options.get_or("max-value", 0)?),
+        None => None,
+    };
+    // `-` reads the lists from standard input
+    let stats = match input_path.to_str() {
+        Some("-") => list_stats(io::stdin().lock(), max_value)?,
+        _ => list_stats(
+            BufReader::new(File::open(input_path).expect("Error reading file")),
+            max_value,
+        )?,
+    };
+    if options.flag("stats") {
+        println!("pairs: {}", stats.pairs);
+        println!("mismatched pairs: {}", stats.mismatched);
+        if let Some(double_median) = stats.double_median() {
+            match double_median % 2 {
+                0 => println!("median distance: {}", double_median / 2),
+                _ => println!("median distance: {}.5", double_median / 2),
+            }
+        }
+        println!("distance histogram:");
+        for (distance, count) in &stats.distances {
+            println!("  {}: {}", distance, count);
+        }
+    }
+    Ok((
+        stats.total_distance.to_string(),
+        stats.similarity.to_string(),
+    ))
+}
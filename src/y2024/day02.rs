@@ -0,0 +1,119 @@
+use std::fs::read_to_string;
+use std::path::Path;
+
+use anyhow::{bail, Result};
+use itertools::Itertools;
+
+use crate::options::Options;
+
+/// Allowed difference between adjacent levels, in the report's direction.
+#[derive(Clone, Copy, Debug)]
+struct StepBounds {
+    min: isize,
+    max: isize,
+}
+
+impl StepBounds {
+    fn allows(&self, from: isize, to: isize, increasing: bool) -> bool {
+        let step = match increasing {
+            true => to - from,
+            false => from - to,
+        };
+        (self.min..=self.max).contains(&step)
+    }
+}
+
+/// Fewest levels to remove so the report is safe while increasing (or decreasing), provided at
+/// most `max_removals` are needed. Returns the removed indices.
+///
+/// `removals[i]` is the fewest removals among levels `0..i` for a safe run ending with level `i`
+/// kept. A level more than `max_removals + 1` positions back can't precede `i` without too many
+/// removals in between, so this takes O(n * max_removals) time.
+fn removals_in_direction(
+    report: &[isize],
+    bounds: StepBounds,
+    max_removals: usize,
+    increasing: bool,
+) -> Option<Vec<usize>> {
+    let n = report.len();
+    let mut removals: Vec<Option<usize>> = vec![None; n];
+    let mut previous: Vec<Option<usize>> = vec![None; n];
+    for i in 0..n {
+        if i <= max_removals {
+            removals[i] = Some(i);
+        }
+        for j in i.saturating_sub(max_removals + 1)..i {
+            let Some(before) = removals[j] else {
+                continue;
+            };
+            let total = before + (i - j - 1);
+            let better = removals[i].is_none_or(|r| total < r);
+            if total <= max_removals && better && bounds.allows(report[j], report[i], increasing) {
+                removals[i] = Some(total);
+                previous[i] = Some(j);
+            }
+        }
+    }
+    let (last, _) = (n.saturating_sub(max_removals + 1)..n)
+        .filter_map(|i| removals[i].map(|r| (i, r + (n - 1 - i))))
+        .filter(|&(_, total)| total <= max_removals)
+        .min_by_key(|&(_, total)| total)?;
+    let mut kept = vec![false; n];
+    let mut cursor = Some(last);
+    while let Some(i) = cursor {
+        kept[i] = true;
+        cursor = previous[i];
+    }
+    Some((0..n).filter(|&i| !kept[i]).collect())
+}
+
+/// The "problem dampener": fewest levels to remove so the report is safe, if at most
+/// `max_removals` are needed. An empty result means the report is already safe.
+fn dampen(report: &[isize], bounds: StepBounds, max_removals: usize) -> Option<Vec<usize>> {
+    if report.is_empty() {
+        return Some(vec![]);
+    }
+    [true, false]
+        .into_iter()
+        .filter_map(|increasing| removals_in_direction(report, bounds, max_removals, increasing))
+        .min_by_key(|removed| removed.len())
+}
+
+pub fn day02(input_path: &Path, options: &Options) -> Result<(String, String)> {
+    let contents: String = read_to_string(input_path).expect("Error reading file");
+    let bounds = StepBounds {
+        min: options.get_or("min-step", 1)?,
+        max: options.get_or("max-step", 3)?,
+    };
+    if bounds.min > bounds.max {
+        bail!(
+            "Minimum step {} is larger than maximum step {}",
+            bounds.min,
+            bounds.max
+        );
+    }
+    let max_removals: usize = options.get_or("max-removals", 1)?;
+    let show_removals = options.flag("removals");
+    let mut p1: usize = 0;
+    let mut p2: usize = 0;
+    for (n, line) in contents.split("\n").enumerate() {
+        let report: Vec<isize> = line.split_whitespace().map(|t| t.parse()).try_collect()?;
+        let Some(removed) = dampen(&report, bounds, max_removals) else {
+            continue;
+        };
+        if removed.is_empty() {
+            p1 += 1;
+        } else if show_removals {
+            println!(
+                "report {}: safe after removing {}",
+                n + 1,
+                removed
+                    .iter()
+                    .map(|&i| format!("{} at index {}", report[i], i))
+                    .join(", ")
+            );
+        }
+        p2 += 1;
+    }
+    Ok((p1.to_string(), p2.to_string()))
+}
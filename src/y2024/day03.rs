@@ -0,0 +1,219 @@
+use std::fs::read_to_string;
+use std::path::Path;
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, Result};
+use itertools::Itertools;
+
+use crate::arith;
+use crate::options::Options;
+
+/// Day reported in arithmetic overflow errors.
+const DAY: u8 = 3;
+
+/// The puzzle's instruction set, in the format accepted by `--instructions`.
+const DEFAULT_INSTRUCTIONS: &str = "mul:3";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Operation {
+    Add,
+    Sub,
+    Mul,
+}
+
+/// A binary instruction `name(a,b)` whose operands have between 1 and `max_digits` digits.
+#[derive(Clone, Copy, Debug)]
+struct InstructionSpec {
+    name: &'static str,
+    operation: Operation,
+    max_digits: usize,
+}
+
+impl FromStr for InstructionSpec {
+    type Err = anyhow::Error;
+
+    /// Parses `name:max_digits`, e.g. `mul:3`.
+    fn from_str(s: &str) -> Result<Self> {
+        let (name, max_digits) = s
+            .split_once(':')
+            .ok_or_else(|| anyhow!("Expected 'name:max_digits', got '{}'", s))?;
+        let (name, operation) = match name {
+            "add" => ("add", Operation::Add),
+            "sub" => ("sub", Operation::Sub),
+            "mul" => ("mul", Operation::Mul),
+            _ => bail!("Unknown instruction '{}'", name),
+        };
+        let max_digits = max_digits
+            .parse()
+            .map_err(|_| anyhow!("Invalid digit limit '{}' for '{}'", max_digits, name))?;
+        Ok(Self {
+            name,
+            operation,
+            max_digits,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Token {
+    Do,
+    Dont,
+    Call {
+        spec: InstructionSpec,
+        lhs: i64,
+        rhs: i64,
+    },
+}
+
+/// Reads the input one byte at a time. Line breaks inside an instruction are skipped, so
+/// instructions that were split over several lines are still recognized.
+#[derive(Clone, Copy)]
+struct Cursor<'a> {
+    input: &'a [u8],
+    pos: usize,
+}
+
+impl Cursor<'_> {
+    fn peek(&mut self) -> Option<u8> {
+        while matches!(self.input.get(self.pos), Some(b'\n' | b'\r')) {
+            self.pos += 1;
+        }
+        self.input.get(self.pos).copied()
+    }
+
+    fn literal(&mut self, literal: &str) -> bool {
+        for expected in literal.bytes() {
+            if self.peek() != Some(expected) {
+                return false;
+            }
+            self.pos += 1;
+        }
+        true
+    }
+
+    fn number(&mut self, max_digits: usize) -> Option<i64> {
+        let mut value: i64 = 0;
+        let mut digits = 0;
+        while let Some(c @ b'0'..=b'9') = self.peek() {
+            if digits == max_digits {
+                return None;
+            }
+            value = value.checked_mul(10)?.checked_add((c - b'0') as i64)?;
+            digits += 1;
+            self.pos += 1;
+        }
+        (digits > 0).then_some(value)
+    }
+
+    fn call(&mut self, spec: InstructionSpec) -> Option<Token> {
+        if !self.literal(spec.name) || !self.literal("(") {
+            return None;
+        }
+        let lhs = self.number(spec.max_digits)?;
+        if !self.literal(",") {
+            return None;
+        }
+        let rhs = self.number(spec.max_digits)?;
+        self.literal(")").then_some(Token::Call { spec, lhs, rhs })
+    }
+}
+
+/// Finds every well-formed instruction in corrupted memory, along with its byte offset and text.
+struct Tokenizer<'a> {
+    input: &'a str,
+    specs: &'a [InstructionSpec],
+    pos: usize,
+}
+
+impl<'a> Tokenizer<'a> {
+    fn new(input: &'a str, specs: &'a [InstructionSpec]) -> Self {
+        Self {
+            input,
+            specs,
+            pos: 0,
+        }
+    }
+
+    fn token_at(&self, pos: usize) -> Option<(Token, usize)> {
+        let start = Cursor {
+            input: self.input.as_bytes(),
+            pos,
+        };
+        let mut cursor = start;
+        if cursor.literal("do()") {
+            return Some((Token::Do, cursor.pos));
+        }
+        cursor = start;
+        if cursor.literal("don't()") {
+            return Some((Token::Dont, cursor.pos));
+        }
+        self.specs.iter().find_map(|&spec| {
+            let mut cursor = start;
+            cursor.call(spec).map(|token| (token, cursor.pos))
+        })
+    }
+}
+
+impl Iterator for Tokenizer<'_> {
+    type Item = (usize, Token, String);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.pos < self.input.len() {
+            let start = self.pos;
+            // Instructions never start with a line break
+            if matches!(self.input.as_bytes()[start], b'\n' | b'\r') {
+                self.pos += 1;
+                continue;
+            }
+            match self.token_at(start) {
+                Some((token, end)) => {
+                    self.pos = end;
+                    let text = self.input[start..end].replace(['\n', '\r'], "");
+                    return Some((start, token, text));
+                }
+                None => self.pos += 1,
+            }
+        }
+        None
+    }
+}
+
+pub fn day03(input_path: &Path, options: &Options) -> Result<(String, String)> {
+    let contents: String = read_to_string(input_path).expect("Error reading file");
+    let specs: Vec<InstructionSpec> = options
+        .get_str("instructions")
+        .unwrap_or(DEFAULT_INSTRUCTIONS)
+        .split(',')
+        .map(|s| s.parse())
+        .try_collect()?;
+    let trace = options.flag("trace");
+    let mut p1: i64 = 0;
+    let mut p2: i64 = 0;
+    let mut enabled = true;
+    for (offset, token, text) in Tokenizer::new(&contents, &specs) {
+        match token {
+            Token::Do => enabled = true,
+            Token::Dont => enabled = false,
+            Token::Call { spec, lhs, rhs } => {
+                let result = match spec.operation {
+                    Operation::Add => arith::add(DAY, lhs, rhs)?,
+                    Operation::Sub => arith::sub(DAY, lhs, rhs)?,
+                    Operation::Mul => arith::mul(DAY, lhs, rhs)?,
+                };
+                p1 = arith::add(DAY, p1, result)?;
+                if enabled {
+                    p2 = arith::add(DAY, p2, result)?;
+                }
+                if trace {
+                    let state = if enabled { "enabled" } else { "disabled" };
+                    println!("offset {}: {} = {} ({})", offset, text, result, state);
+                }
+                continue;
+            }
+        }
+        if trace {
+            println!("offset {}: {}", offset, text);
+        }
+    }
+    Ok((p1.to_string(), p2.to_string()))
+}
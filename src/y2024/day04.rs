@@ -0,0 +1,267 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::fs::read_to_string;
+use std::path::Path;
+
+use anyhow::{anyhow, bail, Result};
+use itertools::Itertools;
+
+use crate::coord::{Coord, DIAGONAL_DIRECTIONS, ORTHOGONAL_DIRECTIONS};
+use crate::options::Options;
+
+/// Compass names of `DIAGONAL_DIRECTIONS`, in the same order.
+const COMPASS: [&str; 8] = ["N", "NE", "E", "SE", "S", "SW", "W", "NW"];
+
+/// Stencil for part 2: two diagonal "MAS" crossing at the `A`.
+const X_MAS: &str = "M.S/.A./M.S";
+
+fn compass(direction: Coord) -> &'static str {
+    DIAGONAL_DIRECTIONS
+        .iter()
+        .position(|&d| d == direction)
+        .map_or("?", |i| COMPASS[i])
+}
+
+fn parse_directions(spec: &str) -> Result<Vec<Coord>> {
+    match spec {
+        "all" => Ok(DIAGONAL_DIRECTIONS.to_vec()),
+        "orthogonal" => Ok(ORTHOGONAL_DIRECTIONS.to_vec()),
+        _ => spec
+            .split(',')
+            .map(|name| {
+                COMPASS
+                    .iter()
+                    .position(|&c| c.eq_ignore_ascii_case(name))
+                    .map(|i| DIAGONAL_DIRECTIONS[i])
+                    .ok_or_else(|| anyhow!("Unknown direction '{}'", name))
+            })
+            .try_collect(),
+    }
+}
+
+/// One of the eight symmetries of a square: an optional mirror image, then clockwise quarter turns.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Orientation {
+    pub reflected: bool,
+    pub quarter_turns: u8,
+}
+
+impl Orientation {
+    const ALL: [Orientation; 8] = [
+        Orientation::new(false, 0),
+        Orientation::new(false, 1),
+        Orientation::new(false, 2),
+        Orientation::new(false, 3),
+        Orientation::new(true, 0),
+        Orientation::new(true, 1),
+        Orientation::new(true, 2),
+        Orientation::new(true, 3),
+    ];
+
+    const fn new(reflected: bool, quarter_turns: u8) -> Self {
+        Self {
+            reflected,
+            quarter_turns,
+        }
+    }
+
+    fn apply(&self, coord: Coord) -> Coord {
+        let mut coord = match self.reflected {
+            true => Coord(coord.0, -coord.1),
+            false => coord,
+        };
+        for _ in 0..self.quarter_turns {
+            coord = Coord(coord.1, -coord.0);
+        }
+        coord
+    }
+}
+
+impl fmt::Display for Orientation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "rotated {}°", self.quarter_turns as usize * 90)?;
+        if self.reflected {
+            write!(f, ", mirrored")?;
+        }
+        Ok(())
+    }
+}
+
+/// A 2D pattern of letters, where `.` matches any letter.
+#[derive(Clone, Debug)]
+pub struct Stencil {
+    /// Letters relative to the top left corner of the stencil's bounding box
+    cells: Vec<(Coord, char)>,
+}
+
+impl Stencil {
+    /// Parses a stencil given as rows separated by `/`, e.g. `M.S/.A./M.S`.
+    pub fn parse(spec: &str) -> Result<Self> {
+        let cells: Vec<(Coord, char)> = spec
+            .split('/')
+            .enumerate()
+            .flat_map(|(i, row)| {
+                row.chars()
+                    .enumerate()
+                    .filter(|&(_, c)| c != '.')
+                    .map(move |(j, c)| (Coord(i as isize, j as isize), c))
+            })
+            .collect();
+        if cells.is_empty() {
+            bail!("Stencil '{}' has no letters", spec);
+        }
+        Ok(Self { cells })
+    }
+
+    fn oriented(&self, orientation: Orientation) -> Self {
+        let cells: Vec<(Coord, char)> = self
+            .cells
+            .iter()
+            .map(|&(coord, c)| (orientation.apply(coord), c))
+            .collect();
+        let min_i = cells.iter().map(|(coord, _)| coord.0).min().unwrap();
+        let min_j = cells.iter().map(|(coord, _)| coord.1).min().unwrap();
+        let mut cells: Vec<(Coord, char)> = cells
+            .into_iter()
+            .map(|(coord, c)| (coord - Coord(min_i, min_j), c))
+            .collect();
+        cells.sort_by_key(|&(coord, c)| (coord.0, coord.1, c));
+        Self { cells }
+    }
+
+    /// Each distinct image of the stencil under the given orientations, with the first
+    /// orientation that produced it. Symmetric stencils yield fewer than eight images.
+    fn orientations(&self, orientations: &[Orientation]) -> Vec<(Orientation, Stencil)> {
+        let mut seen: HashSet<Vec<(Coord, char)>> = HashSet::new();
+        orientations
+            .iter()
+            .map(|&o| (o, self.oriented(o)))
+            .filter(|(_, stencil)| seen.insert(stencil.cells.clone()))
+            .collect()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct WordMatch {
+    pub word: String,
+    pub start: Coord,
+    pub direction: Coord,
+}
+
+#[derive(Clone, Debug)]
+pub struct StencilMatch {
+    /// Top left corner of the oriented stencil's bounding box
+    pub start: Coord,
+    pub orientation: Orientation,
+}
+
+pub struct WordSearch {
+    grid: HashMap<Coord, char>,
+    rows: isize,
+    cols: isize,
+    /// Whether words and stencils may continue across the grid's edges onto the opposite side.
+    wrap: bool,
+}
+
+impl WordSearch {
+    fn get(&self, coord: Coord) -> Option<&char> {
+        match self.wrap {
+            true => self.grid.get(&Coord(
+                coord.0.rem_euclid(self.rows),
+                coord.1.rem_euclid(self.cols),
+            )),
+            false => self.grid.get(&coord),
+        }
+    }
+
+    fn cells(&self) -> impl Iterator<Item = Coord> + '_ {
+        (0..self.rows).flat_map(move |i| (0..self.cols).map(move |j| Coord(i, j)))
+    }
+
+    /// Every occurrence of each word read in a straight line along one of the directions.
+    pub fn find_words(&self, words: &[&str], directions: &[Coord]) -> Vec<WordMatch> {
+        let mut matches = vec![];
+        for start in self.cells() {
+            for word in words {
+                for &direction in directions {
+                    let found = word
+                        .chars()
+                        .enumerate()
+                        .all(|(k, c)| self.get(start + direction * k as isize) == Some(&c));
+                    if found {
+                        matches.push(WordMatch {
+                            word: word.to_string(),
+                            start,
+                            direction,
+                        });
+                    }
+                }
+            }
+        }
+        matches
+    }
+
+    /// Every placement of the stencil in any of the given orientations.
+    pub fn find_stencil(
+        &self,
+        stencil: &Stencil,
+        orientations: &[Orientation],
+    ) -> Vec<StencilMatch> {
+        let images = stencil.orientations(orientations);
+        let mut matches = vec![];
+        for start in self.cells() {
+            for (orientation, image) in &images {
+                let found = image
+                    .cells
+                    .iter()
+                    .all(|&(offset, c)| self.get(start + offset) == Some(&c));
+                if found {
+                    matches.push(StencilMatch {
+                        start,
+                        orientation: *orientation,
+                    });
+                }
+            }
+        }
+        matches
+    }
+}
+
+pub fn day04(input_path: &Path, options: &Options) -> Result<(String, String)> {
+    let contents: String = read_to_string(input_path).expect("Error reading file");
+    let mut grid: HashMap<Coord, char> = HashMap::new();
+    let (mut max_i, mut max_j) = (0, 0);
+    for (i, line) in contents.split("\n").enumerate() {
+        max_i = max_i.max(i as isize);
+        for (j, c) in line.char_indices() {
+            max_j = max_j.max(j as isize);
+            grid.insert(Coord(i as isize, j as isize), c);
+        }
+    }
+    let search = WordSearch {
+        grid,
+        rows: max_i + 1,
+        cols: max_j + 1,
+        wrap: options.flag("wrap"),
+    };
+    let words: Vec<&str> = options
+        .get_str("words")
+        .unwrap_or("XMAS")
+        .split(',')
+        .collect();
+    let directions = parse_directions(options.get_str("directions").unwrap_or("all"))?;
+    let stencil = Stencil::parse(options.get_str("stencil").unwrap_or(X_MAS))?;
+    let word_matches = search.find_words(&words, &directions);
+    let stencil_matches = search.find_stencil(&stencil, &Orientation::ALL);
+    if options.flag("list") {
+        for m in &word_matches {
+            println!("{} at {} going {}", m.word, m.start, compass(m.direction));
+        }
+        for m in &stencil_matches {
+            println!("stencil at {} {}", m.start, m.orientation);
+        }
+    }
+    let p1 = word_matches.len();
+    let p2 = stencil_matches.len();
+    Ok((p1.to_string(), p2.to_string()))
+}
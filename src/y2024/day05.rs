@@ -0,0 +1,343 @@
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::fmt;
+use std::fs::read_to_string;
+use std::path::Path;
+
+use anyhow::Result;
+use itertools::Itertools;
+
+use crate::options::Options;
+
+/// Page ordering rules `X|Y`, stored as edges from X to every Y that must come after it.
+#[derive(Default)]
+struct RuleGraph {
+    successors: HashMap<i32, HashSet<i32>>,
+}
+
+/// One update's pages, ordered by the rules that apply between them.
+enum TopoSort {
+    /// The rules fix a single order.
+    Unique(Vec<i32>),
+    /// Several orders satisfy the rules. Ties were broken by the update's original order.
+    Ambiguous(Vec<i32>),
+    /// The rules among some of these pages form cycles. Each cycle's pages are kept together in
+    /// their original order, and every other rule is satisfied.
+    Cyclic {
+        ordered: Vec<i32>,
+        cycles: Vec<Vec<i32>>,
+    },
+}
+
+/// A rule `before|after` broken by an update, with the 0-based positions of both pages.
+struct Violation {
+    before: i32,
+    after: i32,
+    before_position: usize,
+    after_position: usize,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}|{}: {} at position {} comes before {} at position {}",
+            self.before,
+            self.after,
+            self.after,
+            self.after_position + 1,
+            self.before,
+            self.before_position + 1
+        )
+    }
+}
+
+impl TopoSort {
+    /// The corrected order.
+    fn pages(&self) -> &[i32] {
+        match self {
+            TopoSort::Unique(ordered)
+            | TopoSort::Ambiguous(ordered)
+            | TopoSort::Cyclic { ordered, .. } => ordered,
+        }
+    }
+}
+
+fn middle(pages: &[i32]) -> usize {
+    pages[pages.len() / 2] as usize
+}
+
+impl RuleGraph {
+    fn add_rule(&mut self, src: i32, dst: i32) {
+        self.successors.entry(src).or_default().insert(dst);
+    }
+
+    fn successors_of(&self, page: i32) -> impl Iterator<Item = i32> + '_ {
+        self.successors.get(&page).into_iter().flatten().copied()
+    }
+
+    /// Every rule broken by a pair of pages in the update, adjacent or not.
+    fn violations(&self, pages: &[i32]) -> Vec<Violation> {
+        let positions: HashMap<i32, usize> =
+            pages.iter().enumerate().map(|(i, &p)| (p, i)).collect();
+        let mut violations = vec![];
+        for (i, &page) in pages.iter().enumerate() {
+            for successor in self.successors_of(page) {
+                if let Some(&j) = positions.get(&successor).filter(|&&j| j < i) {
+                    violations.push(Violation {
+                        before: page,
+                        after: successor,
+                        before_position: i,
+                        after_position: j,
+                    });
+                }
+            }
+        }
+        violations.sort_by_key(|v| (v.after_position, v.before_position));
+        violations
+    }
+
+    /// Returns the middle page if no rule is broken.
+    fn check_ordering(&self, pages: &[i32]) -> Option<usize> {
+        self.violations(pages).is_empty().then(|| middle(pages))
+    }
+
+    /// The rules that apply between the given pages.
+    fn restricted_to(&self, pages: &[i32]) -> RuleGraph {
+        let members: HashSet<i32> = pages.iter().copied().collect();
+        let mut graph = RuleGraph::default();
+        for &page in pages {
+            for successor in self.successors_of(page).filter(|s| members.contains(s)) {
+                graph.add_rule(page, successor);
+            }
+        }
+        graph
+    }
+
+    /// Kahn's algorithm over the rules restricted to the given pages. Pages whose rules form a
+    /// cycle are condensed into one node, which is placed as a whole.
+    fn sort(&self, pages: &[i32]) -> TopoSort {
+        let positions: HashMap<i32, usize> =
+            pages.iter().enumerate().map(|(i, &p)| (p, i)).collect();
+        let mut cycles = self.restricted_to(pages).cyclic_components();
+        for cycle in &mut cycles {
+            cycle.sort_by_key(|page| positions[page]);
+        }
+        cycles.sort_by_key(|cycle| positions[&cycle[0]]);
+        // Each node is named by the position of its earliest page, and lists its pages in order
+        let mut node: Vec<usize> = (0..pages.len()).collect();
+        for cycle in &cycles {
+            let first = positions[&cycle[0]];
+            for page in cycle {
+                node[positions[page]] = first;
+            }
+        }
+        let mut members: Vec<Vec<usize>> = vec![vec![]; pages.len()];
+        for i in 0..pages.len() {
+            members[node[i]].push(i);
+        }
+        // Nodes that must come after page `i`, one entry per rule
+        let successor_nodes = |i: usize| -> Vec<usize> {
+            self.successors_of(pages[i])
+                .filter_map(|successor| positions.get(&successor).map(|&j| node[j]))
+                .filter(|&n| n != node[i])
+                .collect()
+        };
+        let mut in_degree: Vec<usize> = vec![0; pages.len()];
+        for i in 0..pages.len() {
+            for n in successor_nodes(i) {
+                in_degree[n] += 1;
+            }
+        }
+        // Ready nodes, earliest in the original update first
+        let mut ready: BinaryHeap<Reverse<usize>> = (0..pages.len())
+            .filter(|&n| !members[n].is_empty() && in_degree[n] == 0)
+            .map(Reverse)
+            .collect();
+        let mut ordered: Vec<i32> = Vec::with_capacity(pages.len());
+        let mut unique = true;
+        while let Some(Reverse(n)) = ready.pop() {
+            unique &= ready.is_empty();
+            for &i in &members[n] {
+                ordered.push(pages[i]);
+                for successor in successor_nodes(i) {
+                    in_degree[successor] -= 1;
+                    if in_degree[successor] == 0 {
+                        ready.push(Reverse(successor));
+                    }
+                }
+            }
+        }
+        match (cycles.is_empty(), unique) {
+            (false, _) => TopoSort::Cyclic { ordered, cycles },
+            (true, true) => TopoSort::Unique(ordered),
+            (true, false) => TopoSort::Ambiguous(ordered),
+        }
+    }
+
+    /// Strongly connected components with more than one page, i.e. groups of pages whose rules
+    /// contradict each other when all applied at once. Uses Tarjan's algorithm.
+    fn cyclic_components(&self) -> Vec<Vec<i32>> {
+        struct Tarjan<'a> {
+            graph: &'a RuleGraph,
+            index: HashMap<i32, usize>,
+            low_link: HashMap<i32, usize>,
+            stack: Vec<i32>,
+            on_stack: HashSet<i32>,
+            components: Vec<Vec<i32>>,
+        }
+
+        impl Tarjan<'_> {
+            fn visit(&mut self, page: i32) {
+                let index = self.index.len();
+                self.index.insert(page, index);
+                self.low_link.insert(page, index);
+                self.stack.push(page);
+                self.on_stack.insert(page);
+                for successor in self.graph.successors_of(page) {
+                    if !self.index.contains_key(&successor) {
+                        self.visit(successor);
+                        let low = self.low_link[&page].min(self.low_link[&successor]);
+                        self.low_link.insert(page, low);
+                    } else if self.on_stack.contains(&successor) {
+                        let low = self.low_link[&page].min(self.index[&successor]);
+                        self.low_link.insert(page, low);
+                    }
+                }
+                if self.low_link[&page] == self.index[&page] {
+                    let mut component = vec![];
+                    while let Some(member) = self.stack.pop() {
+                        self.on_stack.remove(&member);
+                        component.push(member);
+                        if member == page {
+                            break;
+                        }
+                    }
+                    if component.len() > 1 {
+                        component.sort();
+                        self.components.push(component);
+                    }
+                }
+            }
+        }
+
+        let mut tarjan = Tarjan {
+            graph: self,
+            index: HashMap::new(),
+            low_link: HashMap::new(),
+            stack: vec![],
+            on_stack: HashSet::new(),
+            components: vec![],
+        };
+        for &page in self.successors.keys().sorted() {
+            if !tarjan.index.contains_key(&page) {
+                tarjan.visit(page);
+            }
+        }
+        tarjan.components
+    }
+
+    /// Shortest cycle through the first page of a cyclic component, found by BFS.
+    fn example_cycle(&self, component: &[i32]) -> Vec<i32> {
+        let members: HashSet<i32> = component.iter().copied().collect();
+        let start = component[0];
+        let mut parent: HashMap<i32, i32> = HashMap::new();
+        let mut queue: VecDeque<i32> = VecDeque::from([start]);
+        while let Some(page) = queue.pop_front() {
+            for successor in self
+                .successors_of(page)
+                .filter(|s| members.contains(s))
+                .sorted()
+            {
+                if successor == start {
+                    let mut cycle = vec![start];
+                    let mut cursor = page;
+                    while cursor != start {
+                        cycle.push(cursor);
+                        cursor = parent[&cursor];
+                    }
+                    cycle.push(start);
+                    cycle.reverse();
+                    return cycle;
+                }
+                if let Entry::Vacant(e) = parent.entry(successor) {
+                    e.insert(page);
+                    queue.push_back(successor);
+                }
+            }
+        }
+        vec![]
+    }
+}
+
+pub fn day05(input_path: &Path, options: &Options) -> Result<(String, String)> {
+    let contents: String = read_to_string(input_path).expect("Error reading file");
+    let (rules_inp, pages) = contents.split_once("\n\n").unwrap();
+    let diagnostics = options.flag("diagnostics");
+    let report = options.flag("report");
+    let mut p1: usize = 0;
+    let mut p2: usize = 0;
+    let mut rules = RuleGraph::default();
+    for line in rules_inp.split("\n") {
+        let (src, dst) = line.split_once('|').unwrap();
+        rules.add_rule(src.parse()?, dst.parse()?);
+    }
+    if diagnostics {
+        for component in rules.cyclic_components() {
+            println!(
+                "rules are cyclic among {} pages, e.g. {}",
+                component.len(),
+                rules.example_cycle(&component).iter().join(" -> ")
+            );
+        }
+    }
+    for (n, line) in pages.split('\n').enumerate() {
+        let pages: Vec<i32> = line.split(',').map(|t| t.parse::<i32>().unwrap()).collect();
+        if let Some(addend) = rules.check_ordering(pages.as_slice()) {
+            p1 += addend;
+            continue;
+        }
+        let sorted = rules.sort(&pages);
+        if report {
+            println!(
+                "update {} ({}) is out of order:",
+                n + 1,
+                pages.iter().join(",")
+            );
+            for violation in rules.violations(&pages) {
+                println!("  broken rule {}", violation);
+            }
+            match &sorted {
+                TopoSort::Cyclic { .. } => println!(
+                    "  corrected: {} (fallback, the rules are cyclic)",
+                    sorted.pages().iter().join(",")
+                ),
+                _ => println!("  corrected: {}", sorted.pages().iter().join(",")),
+            }
+        }
+        match &sorted {
+            TopoSort::Unique(_) => {}
+            TopoSort::Ambiguous(ordered) => {
+                if diagnostics {
+                    println!(
+                        "update {}: rules allow several orders, using {}",
+                        n + 1,
+                        ordered.iter().join(",")
+                    );
+                }
+            }
+            TopoSort::Cyclic { ordered, cycles } => eprintln!(
+                "Warning: update {}: rules are cyclic among pages {}, falling back to {}",
+                n + 1,
+                cycles
+                    .iter()
+                    .map(|cycle| cycle.iter().join(","))
+                    .join(" and "),
+                ordered.iter().join(",")
+            ),
+        }
+        p2 += middle(sorted.pages());
+    }
+    Ok((p1.to_string(), p2.to_string()))
+}
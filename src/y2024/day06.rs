@@ -0,0 +1,311 @@
+use std::collections::{HashMap, HashSet};
+use std::fs::{read_to_string, write};
+use std::path::Path;
+use std::thread;
+
+use anyhow::{bail, Result};
+
+use crate::coord::{Coord, OrthogonalDirection};
+use crate::options::Options;
+
+const DIRECTIONS: [OrthogonalDirection; 4] = [
+    OrthogonalDirection::UP,
+    OrthogonalDirection::RIGHT,
+    OrthogonalDirection::DOWN,
+    OrthogonalDirection::LEFT,
+];
+
+#[inline]
+fn is_in_grid(coord: Coord, max_i: isize, max_j: isize) -> bool {
+    coord.0 >= 0 && coord.1 >= 0 && coord.0 <= max_i && coord.1 <= max_j
+}
+
+fn simulate(
+    obstacles: &HashSet<Coord>,
+    mut position: Coord,
+    max_i: isize,
+    max_j: isize,
+) -> Option<HashSet<Coord>> {
+    let mut visited: HashSet<Coord> = HashSet::new();
+    let mut states: HashSet<(Coord, OrthogonalDirection)> = HashSet::new();
+    let mut direction = OrthogonalDirection::UP;
+    while is_in_grid(position, max_i, max_j) {
+        if states.contains(&(position, direction)) {
+            return None;
+        }
+        visited.insert(position);
+        states.insert((position, direction));
+        let dst = position + direction.coord();
+        match obstacles.contains(&dst) {
+            true => direction = direction.cw(),
+            false => position = dst,
+        }
+    }
+    Some(visited)
+}
+
+/// For every cell and direction, the cell where the guard stops in front of the next obstacle,
+/// or None if the guard walks off the grid first. Lets loop checks skip straight lines entirely.
+struct JumpTable {
+    stops: HashMap<(Coord, OrthogonalDirection), Option<Coord>>,
+}
+
+impl JumpTable {
+    fn new(obstacles: &HashSet<Coord>, max_i: isize, max_j: isize) -> Self {
+        let mut stops = HashMap::new();
+        for direction in DIRECTIONS {
+            let step = direction.coord();
+            let mut cells: Vec<Coord> = (0..=max_i)
+                .flat_map(|i| (0..=max_j).map(move |j| Coord(i, j)))
+                .collect();
+            // Fill in the cells furthest along the direction first, so each cell's next cell is known.
+            cells.sort_by_key(|c| -(c.0 * step.0 + c.1 * step.1));
+            for cell in cells {
+                let next = cell + step;
+                let stop = match (is_in_grid(next, max_i, max_j), obstacles.contains(&next)) {
+                    (false, _) => None,
+                    (true, true) => Some(cell),
+                    (true, false) => *stops.get(&(next, direction)).unwrap(),
+                };
+                stops.insert((cell, direction), stop);
+            }
+        }
+        Self { stops }
+    }
+
+    /// Where the guard stops when walking from `position`, with one extra obstacle placed at `extra`.
+    fn stop(&self, position: Coord, direction: OrthogonalDirection, extra: Coord) -> Option<Coord> {
+        let stop = *self.stops.get(&(position, direction)).unwrap();
+        let step = direction.coord();
+        let offset = extra - position;
+        // Distance to `extra` along the direction, if it lies straight ahead
+        let ahead = match step {
+            Coord(0, s) if offset.0 == 0 && offset.1 * s > 0 => offset.1 * s,
+            Coord(s, 0) if offset.1 == 0 && offset.0 * s > 0 => offset.0 * s,
+            _ => return stop,
+        };
+        let reach = match stop {
+            Some(stop) => (stop - position).0.abs() + (stop - position).1.abs(),
+            None => isize::MAX,
+        };
+        match ahead <= reach {
+            true => Some(extra - step),
+            false => stop,
+        }
+    }
+
+    /// Walks the guard's path with an obstacle placed at `extra`, jumping between turning points.
+    /// Returns the path's turning points if the guard gets stuck in a loop.
+    fn find_loop(&self, mut position: Coord, extra: Coord) -> Option<LoopReport> {
+        let mut direction = OrthogonalDirection::UP;
+        let mut turns: Vec<(Coord, OrthogonalDirection)> = vec![];
+        let mut seen: HashMap<(Coord, OrthogonalDirection), usize> = HashMap::new();
+        while let Some(stop) = self.stop(position, direction, extra) {
+            position = stop;
+            if let Some(&cycle_start) = seen.get(&(position, direction)) {
+                return Some(LoopReport {
+                    obstruction: extra,
+                    turns,
+                    cycle_start,
+                });
+            }
+            seen.insert((position, direction), turns.len());
+            turns.push((position, direction));
+            direction = direction.cw();
+        }
+        None
+    }
+}
+
+#[inline]
+fn distance(a: Coord, b: Coord) -> usize {
+    a.0.abs_diff(b.0) + a.1.abs_diff(b.1)
+}
+
+fn direction_name(direction: OrthogonalDirection) -> &'static str {
+    match direction {
+        OrthogonalDirection::UP => "up",
+        OrthogonalDirection::RIGHT => "right",
+        OrthogonalDirection::DOWN => "down",
+        OrthogonalDirection::LEFT => "left",
+    }
+}
+
+/// A loop caused by placing a single obstruction.
+struct LoopReport {
+    obstruction: Coord,
+    /// Every point where the guard turned, with the direction it was walking when it got there.
+    /// From `cycle_start` onwards the points repeat forever.
+    turns: Vec<(Coord, OrthogonalDirection)>,
+    cycle_start: usize,
+}
+
+impl LoopReport {
+    fn cycle(&self) -> &[(Coord, OrthogonalDirection)] {
+        &self.turns[self.cycle_start..]
+    }
+
+    /// Number of steps the guard takes to walk the cycle once.
+    fn cycle_len(&self) -> usize {
+        let cycle = self.cycle();
+        cycle
+            .iter()
+            .zip(cycle.iter().cycle().skip(1))
+            .map(|(&(a, _), &(b, _))| distance(a, b))
+            .sum()
+    }
+
+    fn to_text(&self) -> String {
+        let cycle = self
+            .cycle()
+            .iter()
+            .map(|(position, direction)| format!("{}{}", position, direction))
+            .collect::<Vec<String>>()
+            .join(" ");
+        format!(
+            "obstruction {}: loop of {} steps through {} turns: {}",
+            self.obstruction,
+            self.cycle_len(),
+            self.cycle().len(),
+            cycle
+        )
+    }
+
+    fn to_json(&self) -> String {
+        let cycle = self
+            .cycle()
+            .iter()
+            .map(|(position, direction)| {
+                format!(
+                    "{{\"position\":[{},{}],\"direction\":\"{}\"}}",
+                    position.0,
+                    position.1,
+                    direction_name(*direction)
+                )
+            })
+            .collect::<Vec<String>>()
+            .join(",");
+        format!(
+            "{{\"obstruction\":[{},{}],\"length\":{},\"cycle\":[{}]}}",
+            self.obstruction.0,
+            self.obstruction.1,
+            self.cycle_len(),
+            cycle
+        )
+    }
+
+    /// Draws the guard's path as in the puzzle statement: `|` and `-` for vertical and horizontal
+    /// movement, `+` where the two cross or the guard turns, and `O` for the new obstruction.
+    fn render(
+        &self,
+        obstacles: &HashSet<Coord>,
+        start: Coord,
+        max_i: isize,
+        max_j: isize,
+    ) -> String {
+        let mut marks: HashMap<Coord, char> = HashMap::new();
+        let mut position = start;
+        let closing = self.turns[self.cycle_start];
+        for &(turn, direction) in self.turns.iter().chain([&closing]) {
+            let mark = match direction {
+                OrthogonalDirection::UP | OrthogonalDirection::DOWN => '|',
+                OrthogonalDirection::LEFT | OrthogonalDirection::RIGHT => '-',
+            };
+            while position != turn {
+                let entry = marks.entry(position).or_insert(mark);
+                if *entry != mark {
+                    *entry = '+';
+                }
+                position += direction.coord();
+            }
+            marks.insert(turn, '+');
+        }
+        let mut rendered = String::new();
+        for i in 0..=max_i {
+            for j in 0..=max_j {
+                let coord = Coord(i, j);
+                rendered.push(match coord {
+                    _ if coord == self.obstruction => 'O',
+                    _ if coord == start => '^',
+                    _ if obstacles.contains(&coord) => '#',
+                    _ => *marks.get(&coord).unwrap_or(&'.'),
+                });
+            }
+            rendered.push('\n');
+        }
+        rendered
+    }
+}
+
+pub fn day06(input_path: &Path, options: &Options) -> Result<(String, String)> {
+    let contents: String = read_to_string(input_path).expect("Error reading file");
+    let mut obstacles: HashSet<Coord> = HashSet::new();
+    let mut position: Coord = Coord(0, 0);
+    let (mut max_i, mut max_j) = (0, 0);
+    for (i, line) in contents.split("\n").enumerate() {
+        max_i = max_i.max(i as isize);
+        for (j, c) in line.char_indices() {
+            max_j = max_j.max(j as isize);
+            match c {
+                '#' => _ = obstacles.insert(Coord(i as isize, j as isize)),
+                '^' => position = Coord(i as isize, j as isize),
+                _ => {}
+            }
+        }
+    }
+    let visited = simulate(&obstacles, position, max_i, max_j).unwrap();
+    let p1 = visited.len();
+    // An obstruction off the guard's original path can never change it.
+    let candidates: Vec<Coord> = visited.into_iter().filter(|&c| c != position).collect();
+    let jump_table = JumpTable::new(&obstacles, max_i, max_j);
+    let workers = thread::available_parallelism().map_or(1, |n| n.get());
+    let chunk_size = candidates.len().div_ceil(workers).max(1);
+    let mut loops: Vec<LoopReport> = thread::scope(|s| {
+        let handles: Vec<_> = candidates
+            .chunks(chunk_size)
+            .map(|chunk| {
+                let jump_table = &jump_table;
+                s.spawn(move || {
+                    chunk
+                        .iter()
+                        .filter_map(|&extra| jump_table.find_loop(position, extra))
+                        .collect::<Vec<LoopReport>>()
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|h| h.join().unwrap())
+            .collect()
+    });
+    loops.sort_by_key(|l| (l.obstruction.0, l.obstruction.1));
+    let p2 = loops.len();
+    let export = match options.get_str("loops") {
+        None => None,
+        Some("text") => Some(loops.iter().map(|l| l.to_text() + "\n").collect::<String>()),
+        Some("json") => Some(format!(
+            "[{}]\n",
+            loops
+                .iter()
+                .map(|l| l.to_json())
+                .collect::<Vec<String>>()
+                .join(",")
+        )),
+        Some(format) => bail!(
+            "Unknown loop export format '{}', expected text or json",
+            format
+        ),
+    };
+    match (export, options.get_str("loops-file")) {
+        (Some(export), Some(file)) => write(file, export)?,
+        (Some(export), None) => print!("{}", export),
+        (None, _) => {}
+    }
+    if options.flag("render-loops") {
+        for report in &loops {
+            println!("obstruction {}:", report.obstruction);
+            println!("{}", report.render(&obstacles, position, max_i, max_j));
+        }
+    }
+    Ok((p1.to_string(), p2.to_string()))
+}
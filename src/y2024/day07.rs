@@ -0,0 +1,362 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::fs::read_to_string;
+use std::ops::RangeInclusive;
+use std::path::Path;
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, Result};
+use itertools::Itertools;
+
+use crate::arith::{self, OverflowError};
+use crate::options::Options;
+
+/// Day reported in arithmetic overflow errors.
+const DAY: u8 = 7;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Operand {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Pow,
+    Xor,
+    Concat,
+}
+
+/// Every running value `x` for which `x <op> value == target`.
+enum Preimage {
+    Values(Vec<i64>),
+    /// Every running value in the range, e.g. the dividends that share a quotient, or all of
+    /// them when the result does not depend on the running value, as for `x * 0`.
+    Range(RangeInclusive<i64>),
+}
+
+impl FromStr for Operand {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(match s {
+            "add" => Operand::Add,
+            "sub" => Operand::Sub,
+            "mul" => Operand::Mul,
+            "div" => Operand::Div,
+            "pow" => Operand::Pow,
+            "xor" => Operand::Xor,
+            "concat" => Operand::Concat,
+            _ => bail!("Unknown operator '{}'", s),
+        })
+    }
+}
+
+impl fmt::Display for Operand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Operand::Add => write!(f, "+"),
+            Operand::Sub => write!(f, "-"),
+            Operand::Mul => write!(f, "*"),
+            Operand::Div => write!(f, "/"),
+            Operand::Pow => write!(f, "**"),
+            Operand::Xor => write!(f, "^"),
+            Operand::Concat => write!(f, "||"),
+        }
+    }
+}
+
+/// Integer `exp`-th roots of `target`, for `exp >= 1`.
+fn roots(target: i64, exp: u32) -> Vec<i64> {
+    if exp == 1 {
+        return vec![target];
+    }
+    // Floating point only estimates the root of |target|, so settle it with integer powers
+    let magnitude = target.unsigned_abs();
+    let fits = |r: u64| r.checked_pow(exp).is_some_and(|power| power <= magnitude);
+    let mut root = (magnitude as f64).powf(1.0 / exp as f64).round() as u64;
+    while root > 0 && !fits(root) {
+        root -= 1;
+    }
+    while fits(root + 1) {
+        root += 1;
+    }
+    if root.pow(exp) != magnitude {
+        return vec![];
+    }
+    let root = root as i64;
+    match (target < 0, exp.is_multiple_of(2)) {
+        (false, true) if root > 0 => vec![-root, root],
+        (false, _) => vec![root],
+        (true, false) => vec![-root],
+        (true, true) => vec![],
+    }
+}
+
+/// Applies the crate's overflow policy to a checked operation: with `checked-arithmetic` an
+/// overflow is reported, otherwise it just leaves the operation undefined.
+fn checked(
+    operation: &'static str,
+    lhs: i64,
+    rhs: impl fmt::Display,
+    result: Option<i64>,
+) -> Result<Option<i64>, OverflowError> {
+    match result {
+        None if cfg!(feature = "checked-arithmetic") => Err(OverflowError {
+            day: DAY,
+            operation,
+            lhs: lhs.to_string(),
+            rhs: rhs.to_string(),
+        }),
+        result => Ok(result),
+    }
+}
+
+impl Operand {
+    /// Evaluates `running <op> value`. Returns None if the operation is undefined.
+    fn apply(&self, running: i64, value: i64) -> Result<Option<i64>, OverflowError> {
+        match self {
+            Operand::Add => checked("addition", running, value, running.checked_add(value)),
+            Operand::Sub => checked("subtraction", running, value, running.checked_sub(value)),
+            Operand::Mul => checked("multiplication", running, value, running.checked_mul(value)),
+            Operand::Div if value == 0 => Ok(None),
+            Operand::Div => checked("division", running, value, running.checked_div(value)),
+            Operand::Pow => match u32::try_from(value) {
+                Ok(exp) => checked("exponentiation", running, exp, running.checked_pow(exp)),
+                Err(_) => Ok(None),
+            },
+            Operand::Xor => Ok(Some(running ^ value)),
+            Operand::Concat => {
+                let digits = value.to_string().len() as u32;
+                let Some(shift) =
+                    checked("exponentiation", 10, digits, 10_i64.checked_pow(digits))?
+                else {
+                    return Ok(None);
+                };
+                let Some(head) =
+                    checked("multiplication", running, shift, running.checked_mul(shift))?
+                else {
+                    return Ok(None);
+                };
+                checked("addition", head, value, head.checked_add(value))
+            }
+        }
+    }
+
+    /// Inverts `x <op> value == target` for `x`. An `x` that would not fit in an i64 is an
+    /// overflow, so it is reported or left out according to [`checked`].
+    fn undo(&self, target: i64, value: i64) -> Result<Preimage, OverflowError> {
+        let values = match self {
+            Operand::Add => checked("subtraction", target, value, target.checked_sub(value))?
+                .into_iter()
+                .collect(),
+            Operand::Sub => checked("addition", target, value, target.checked_add(value))?
+                .into_iter()
+                .collect(),
+            Operand::Mul if value == 0 && target == 0 => {
+                return Ok(Preimage::Range(i64::MIN..=i64::MAX))
+            }
+            Operand::Mul if value == 0 => vec![],
+            Operand::Mul if target.checked_rem(value).is_some_and(|r| r != 0) => vec![],
+            Operand::Mul => checked("division", target, value, target.checked_div(value))?
+                .into_iter()
+                .collect(),
+            Operand::Div if value == 0 => vec![],
+            Operand::Div => {
+                let base = checked("multiplication", target, value, target.checked_mul(value))?;
+                // Division truncates toward zero, so the dividends sharing a quotient run from
+                // `target * value` away from zero for another |value| - 1 values.
+                let spread = (value.unsigned_abs() - 1) as i64;
+                return Ok(match base {
+                    None => Preimage::Values(vec![]),
+                    Some(0) => Preimage::Range(-spread..=spread),
+                    Some(base) if base > 0 => Preimage::Range(base..=base.saturating_add(spread)),
+                    Some(base) => Preimage::Range(base.saturating_sub(spread)..=base),
+                });
+            }
+            Operand::Pow if value == 0 && target == 1 => {
+                return Ok(Preimage::Range(i64::MIN..=i64::MAX))
+            }
+            Operand::Pow => match u32::try_from(value) {
+                Ok(exp) if exp > 0 => roots(target, exp),
+                _ => vec![],
+            },
+            Operand::Xor => vec![target ^ value],
+            Operand::Concat => {
+                let digits = value.to_string().len() as u32;
+                let shift = checked("exponentiation", 10, digits, 10_i64.checked_pow(digits))?;
+                let head = checked("subtraction", target, value, target.checked_sub(value))?;
+                match (shift, head) {
+                    (Some(shift), Some(head)) if head % shift == 0 => vec![head / shift],
+                    _ => vec![],
+                }
+            }
+        };
+        Ok(Preimage::Values(values))
+    }
+
+    /// True if a non-negative running value stays non-negative for any non-negative value.
+    fn preserves_non_negative(&self) -> bool {
+        !matches!(self, Operand::Sub)
+    }
+}
+
+fn render_expression(values: &[i64], ops: &[Operand]) -> String {
+    let mut expression = values.first().unwrap().to_string();
+    for (value, op) in values.iter().skip(1).zip(ops) {
+        expression += &format!(" {} {}", op, value);
+    }
+    expression
+}
+
+struct Solver<'a> {
+    ops: &'a [Operand],
+    /// Running values can never be negative, so negative preimages are pruned.
+    non_negative: bool,
+}
+
+impl<'a> Solver<'a> {
+    fn new(ops: &'a [Operand], values: &[i64]) -> Self {
+        Self {
+            ops,
+            non_negative: values.iter().all(|&v| v >= 0)
+                && ops.iter().all(|op| op.preserves_non_negative()),
+        }
+    }
+
+    /// Works from the last value back to the first, undoing each operator and pruning any
+    /// branch where the inverse does not exist. Returns the operators of a satisfying expression.
+    fn check_equation(
+        &self,
+        result: i64,
+        values: &[i64],
+    ) -> Result<Option<Vec<Operand>>, OverflowError> {
+        let (&last, rest) = values.split_last().unwrap();
+        if rest.is_empty() {
+            return Ok((result == last).then(Vec::new));
+        }
+        for &op in self.ops {
+            let found = match op.undo(result, last)? {
+                Preimage::Values(preimages) => self.check_preimages(preimages, rest)?,
+                // A range can be too wide to undo value by value, so search forward into it
+                Preimage::Range(range) => self.expression_in(rest, &range)?,
+            };
+            if let Some(mut ops) = found {
+                ops.push(op);
+                return Ok(Some(ops));
+            }
+        }
+        Ok(None)
+    }
+
+    /// The operators of a satisfying expression for `values` and any of the `preimages`.
+    fn check_preimages(
+        &self,
+        preimages: Vec<i64>,
+        values: &[i64],
+    ) -> Result<Option<Vec<Operand>>, OverflowError> {
+        for prev in preimages {
+            if self.non_negative && prev < 0 {
+                continue;
+            }
+            if let Some(ops) = self.check_equation(prev, values)? {
+                return Ok(Some(ops));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Any mix of operators for which `values`, evaluated left to right, give a defined result
+    /// within `range`.
+    fn expression_in(
+        &self,
+        values: &[i64],
+        range: &RangeInclusive<i64>,
+    ) -> Result<Option<Vec<Operand>>, OverflowError> {
+        let (&first, rest) = values.split_first().unwrap();
+        let mut ops = Vec::with_capacity(rest.len());
+        let found = self.extend_into(first, rest, range, &mut ops, &mut HashSet::new())?;
+        Ok(found.then_some(ops))
+    }
+
+    /// Depth-first search for operators that keep `running` defined through the rest of
+    /// `values` and end within `range`, pushing them onto `ops`. Running values known to lead
+    /// nowhere, keyed by how many values remain, are kept in `dead_ends`.
+    fn extend_into(
+        &self,
+        running: i64,
+        values: &[i64],
+        range: &RangeInclusive<i64>,
+        ops: &mut Vec<Operand>,
+        dead_ends: &mut HashSet<(usize, i64)>,
+    ) -> Result<bool, OverflowError> {
+        let Some((&value, rest)) = values.split_first() else {
+            return Ok(range.contains(&running));
+        };
+        if dead_ends.contains(&(values.len(), running)) {
+            return Ok(false);
+        }
+        for &op in self.ops {
+            if let Some(next) = op.apply(running, value)? {
+                ops.push(op);
+                if self.extend_into(next, rest, range, ops, dead_ends)? {
+                    return Ok(true);
+                }
+                ops.pop();
+            }
+        }
+        dead_ends.insert((values.len(), running));
+        Ok(false)
+    }
+}
+
+fn parse_ops(options: &Options, key: &str, default: &[Operand]) -> Result<Vec<Operand>> {
+    let ops: Vec<Operand> = match options.get_str(key) {
+        None => return Ok(default.to_vec()),
+        Some(names) => names.split(',').map(|n| n.parse()).try_collect()?,
+    };
+    if ops.is_empty() {
+        return Err(anyhow!("Option '--{}' needs at least one operator", key));
+    }
+    Ok(ops)
+}
+
+pub fn day07(input_path: &Path, options: &Options) -> Result<(String, String)> {
+    let mut p1: usize = 0;
+    let mut p2: usize = 0;
+    let ops_p1 = parse_ops(options, "p1-ops", &[Operand::Add, Operand::Mul])?;
+    let ops_p2 = parse_ops(
+        options,
+        "p2-ops",
+        &[Operand::Add, Operand::Mul, Operand::Concat],
+    )?;
+    // Anything calibrated by the part 1 operators is then also calibrated in part 2.
+    let p2_extends_p1 = ops_p1.iter().all(|op| ops_p2.contains(op));
+    let explain = options.flag("explain");
+    let contents: String = read_to_string(input_path).expect("Error reading file");
+    for line in contents.split("\n") {
+        let (result, values) = line.split_once(':').unwrap();
+        let result: i64 = result.parse().unwrap();
+        let values: Vec<i64> = values
+            .trim()
+            .split(' ')
+            .map(|v| v.parse().unwrap())
+            .collect();
+        let solution_p1 = Solver::new(&ops_p1, &values).check_equation(result, &values)?;
+        let solution_p2 = match (&solution_p1, p2_extends_p1) {
+            (Some(ops), true) => Some(ops.clone()),
+            _ => Solver::new(&ops_p2, &values).check_equation(result, &values)?,
+        };
+        if let Some(ops) = solution_p1 {
+            p1 = arith::add(DAY, p1, result as usize)?;
+            if explain {
+                println!("p1: {} = {}", result, render_expression(&values, &ops));
+            }
+        }
+        if let Some(ops) = solution_p2 {
+            p2 = arith::add(DAY, p2, result as usize)?;
+            if explain {
+                println!("p2: {} = {}", result, render_expression(&values, &ops));
+            }
+        }
+    }
+    Ok((p1.to_string(), p2.to_string()))
+}
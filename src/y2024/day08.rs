@@ -0,0 +1,213 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs::read_to_string;
+use std::path::Path;
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, Result};
+use itertools::Itertools;
+
+use crate::coord::Coord;
+use crate::options::Options;
+
+/// The puzzle's rules, in the format accepted by `--p1-rule` and `--p2-rule`.
+const DEFAULT_P1_RULE: &str = "ratio 2";
+const DEFAULT_P2_RULE: &str = "any";
+
+#[inline]
+fn is_in_grid(coord: Coord, max_i: isize, max_j: isize) -> bool {
+    coord.0 >= 0 && coord.1 >= 0 && coord.0 <= max_i && coord.1 <= max_j
+}
+
+fn gcd(a: isize, b: isize) -> isize {
+    match b {
+        0 => a.abs(),
+        _ => gcd(b, a % b),
+    }
+}
+
+/// Which points in line with a pair of antennas are antinodes.
+///
+/// Points on the line are `a + k * step` for integer `k`. The step is the vector between the
+/// antennas, or that vector divided by its gcd so every grid point on the line is considered,
+/// including those between the antennas.
+#[derive(Clone, Debug)]
+struct AntinodeRule {
+    /// Only points where one antenna is this many times as far away as the other qualify.
+    /// None allows any point in line.
+    ratios: Option<Vec<isize>>,
+    /// Only points at most this many steps beyond the nearer antenna qualify. Points between
+    /// the antennas are always within range.
+    max_harmonics: Option<isize>,
+    reduced: bool,
+}
+
+impl FromStr for AntinodeRule {
+    type Err = anyhow::Error;
+
+    /// Parses clauses separated by `;`: `any` or `ratio <r>[,<r>...]`, then optionally
+    /// `harmonics <n>` and `reduced`.
+    fn from_str(s: &str) -> Result<Self> {
+        let mut rule = AntinodeRule {
+            ratios: None,
+            max_harmonics: None,
+            reduced: false,
+        };
+        let mut clauses = s.split(';').map(|c| c.trim());
+        let first = clauses.next().unwrap();
+        match first.split_once(' ') {
+            None if first == "any" => {}
+            Some(("ratio", ratios)) => {
+                rule.ratios = Some(
+                    ratios
+                        .split(',')
+                        .map(|r| {
+                            r.trim()
+                                .parse()
+                                .map_err(|_| anyhow!("Invalid ratio '{}'", r.trim()))
+                        })
+                        .try_collect()?,
+                )
+            }
+            _ => bail!("Rule '{}' must start with 'any' or 'ratio <r>'", s),
+        }
+        for clause in clauses {
+            match clause.split_once(' ') {
+                None if clause == "reduced" => rule.reduced = true,
+                Some(("harmonics", n)) => {
+                    rule.max_harmonics = Some(
+                        n.trim()
+                            .parse()
+                            .map_err(|_| anyhow!("Invalid harmonics count '{}'", n.trim()))?,
+                    )
+                }
+                _ => bail!("Unknown rule clause '{}'", clause),
+            }
+        }
+        Ok(rule)
+    }
+}
+
+impl AntinodeRule {
+    /// Whether the point `k` steps from `a` qualifies, where `b` is `span` steps from `a`.
+    fn accepts(&self, k: isize, span: isize) -> bool {
+        let (to_a, to_b) = (k.abs(), (k - span).abs());
+        let in_range = match self.max_harmonics {
+            Some(max) => k >= -max && k <= span + max,
+            None => true,
+        };
+        let in_ratio = match &self.ratios {
+            Some(ratios) => ratios.iter().any(|&r| to_a == r * to_b || to_b == r * to_a),
+            None => true,
+        };
+        in_range && in_ratio
+    }
+
+    /// Every antinode of the antennas `a` and `b` within the grid.
+    fn antinodes(&self, a: Coord, b: Coord, max_i: isize, max_j: isize) -> Vec<Coord> {
+        let delta = b - a;
+        let span = match self.reduced {
+            true => gcd(delta.0, delta.1),
+            false => 1,
+        };
+        let step = Coord(delta.0 / span, delta.1 / span);
+        let mut antinodes = vec![];
+        for direction in [1, -1] {
+            // Walk from `a` in both directions until leaving the grid
+            let mut k = if direction == 1 { 0 } else { -1 };
+            while is_in_grid(a + step * k, max_i, max_j) {
+                if self.accepts(k, span) {
+                    antinodes.push(a + step * k);
+                }
+                k += direction;
+            }
+        }
+        antinodes
+    }
+}
+
+/// Antinodes under one rule, with every antenna pair that produces each of them.
+fn find_antinodes(
+    rule: &AntinodeRule,
+    freqs: &BTreeMap<char, Vec<Coord>>,
+    max_i: isize,
+    max_j: isize,
+) -> HashMap<Coord, Vec<(char, Coord, Coord)>> {
+    let mut antinodes: HashMap<Coord, Vec<(char, Coord, Coord)>> = HashMap::new();
+    for (&freq, nodes) in freqs {
+        for (&a, &b) in nodes.iter().tuple_combinations() {
+            for antinode in rule.antinodes(a, b, max_i, max_j) {
+                antinodes.entry(antinode).or_default().push((freq, a, b));
+            }
+        }
+    }
+    antinodes
+}
+
+/// The map with `#` drawn over every antinode that isn't hidden by an antenna.
+fn render(
+    grid: &HashMap<Coord, char>,
+    antinodes: &HashMap<Coord, Vec<(char, Coord, Coord)>>,
+    max_i: isize,
+    max_j: isize,
+) -> String {
+    let mut rendered = String::new();
+    for i in 0..=max_i {
+        for j in 0..=max_j {
+            let coord = Coord(i, j);
+            rendered.push(match grid.get(&coord) {
+                Some(&antenna) => antenna,
+                None if antinodes.contains_key(&coord) => '#',
+                None => '.',
+            });
+        }
+        rendered.push('\n');
+    }
+    rendered
+}
+
+pub fn day08(input_path: &Path, options: &Options) -> Result<(String, String)> {
+    let contents: String = read_to_string(input_path).expect("Error reading file");
+    let rule_p1: AntinodeRule = options
+        .get_str("p1-rule")
+        .unwrap_or(DEFAULT_P1_RULE)
+        .parse()?;
+    let rule_p2: AntinodeRule = options
+        .get_str("p2-rule")
+        .unwrap_or(DEFAULT_P2_RULE)
+        .parse()?;
+    let mut grid: HashMap<Coord, char> = HashMap::new();
+    let mut freqs: BTreeMap<char, Vec<Coord>> = BTreeMap::new();
+    let (mut max_i, mut max_j) = (0, 0);
+    for (i, line) in contents.split("\n").enumerate() {
+        max_i = max_i.max(i as isize);
+        for (j, c) in line.char_indices() {
+            max_j = max_j.max(j as isize);
+            if c == '.' {
+                continue;
+            }
+            let coord = Coord(i as isize, j as isize);
+            grid.insert(coord, c);
+            freqs.entry(c).or_default().push(coord);
+        }
+    }
+    let antinodes_p1 = find_antinodes(&rule_p1, &freqs, max_i, max_j);
+    let antinodes_p2 = find_antinodes(&rule_p2, &freqs, max_i, max_j);
+    for (part, antinodes) in [("p1", &antinodes_p1), ("p2", &antinodes_p2)] {
+        if options.flag("pairs") {
+            for (antinode, pairs) in antinodes.iter().sorted_by_key(|(c, _)| (c.0, c.1)) {
+                let pairs = pairs
+                    .iter()
+                    .map(|(freq, a, b)| format!("'{}' {} & {}", freq, a, b))
+                    .join(", ");
+                println!("{} antinode {}: {}", part, antinode, pairs);
+            }
+        }
+        if options.flag("render") {
+            println!("{}:", part);
+            println!("{}", render(&grid, antinodes, max_i, max_j));
+        }
+    }
+    let p1 = antinodes_p1.len();
+    let p2 = antinodes_p2.len();
+    Ok((p1.to_string(), p2.to_string()))
+}
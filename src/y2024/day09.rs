@@ -0,0 +1,524 @@
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BinaryHeap, HashMap};
+use std::fmt;
+use std::fs::read_to_string;
+use std::path::Path;
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, Result};
+use itertools::Itertools;
+
+use crate::arith::{self, OverflowError};
+use crate::options::Options;
+
+/// Day reported in arithmetic overflow errors.
+const DAY: u8 = 9;
+
+#[derive(PartialEq, Eq, Clone, Copy)]
+pub enum ChunkType {
+    Full(usize),
+    Empty,
+}
+
+#[derive(Clone, Copy)]
+pub struct Chunk {
+    len: usize,
+    chunk_type: ChunkType,
+}
+
+fn checksum(disk_map: &[Chunk]) -> Result<usize, OverflowError> {
+    let mut i: usize = 0;
+    let mut sum = 0;
+    for chunk in disk_map {
+        let id = match chunk.chunk_type {
+            ChunkType::Full(id) => id,
+            ChunkType::Empty => {
+                i += chunk.len;
+                continue;
+            }
+        };
+        // Sum of positions i..i + len, without visiting every block of large files
+        let positions = arith::add(
+            DAY,
+            arith::mul(DAY, i, chunk.len)?,
+            arith::mul(DAY, chunk.len, chunk.len.saturating_sub(1))? / 2,
+        )?;
+        sum = arith::add(DAY, sum, arith::mul(DAY, positions, id)?)?;
+        i += chunk.len;
+    }
+    Ok(sum)
+}
+
+/// Renders one character per block: the file id for full blocks and `.` for free ones.
+/// Ids above 9 continue with lowercase letters and wrap around after `z`.
+fn render(disk_map: &[Chunk]) -> String {
+    let mut rendered = String::new();
+    for chunk in disk_map {
+        let c = match chunk.chunk_type {
+            ChunkType::Full(id) => char::from_digit((id % 36) as u32, 36).unwrap(),
+            ChunkType::Empty => '.',
+        };
+        rendered.extend(std::iter::repeat_n(c, chunk.len));
+    }
+    rendered
+}
+
+#[derive(Debug, Default)]
+pub struct FragmentationStats {
+    pub files: usize,
+    /// Files whose blocks are split over more than one contiguous run.
+    pub fragmented_files: usize,
+    pub free_blocks: usize,
+    /// Number of maximal runs of free blocks.
+    pub free_spans: usize,
+    pub largest_free_span: usize,
+}
+
+impl fmt::Display for FragmentationStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "files: {} (fragmented: {}), free blocks: {} in {} spans (largest: {})",
+            self.files,
+            self.fragmented_files,
+            self.free_blocks,
+            self.free_spans,
+            self.largest_free_span
+        )
+    }
+}
+
+fn fragmentation_stats(disk_map: &[Chunk]) -> FragmentationStats {
+    let mut stats = FragmentationStats::default();
+    let mut runs: HashMap<usize, usize> = HashMap::new();
+    let mut previous: Option<ChunkType> = None;
+    let mut free_span = 0;
+    for chunk in disk_map.iter().filter(|c| c.len > 0) {
+        match chunk.chunk_type {
+            ChunkType::Full(id) => {
+                if previous != Some(chunk.chunk_type) {
+                    *runs.entry(id).or_insert(0) += 1;
+                }
+                free_span = 0;
+            }
+            ChunkType::Empty => {
+                if previous != Some(ChunkType::Empty) {
+                    stats.free_spans += 1;
+                }
+                stats.free_blocks += chunk.len;
+                free_span += chunk.len;
+                stats.largest_free_span = stats.largest_free_span.max(free_span);
+            }
+        }
+        previous = Some(chunk.chunk_type);
+    }
+    stats.files = runs.len();
+    stats.fragmented_files = runs.values().filter(|&&r| r > 1).count();
+    stats
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Strategy {
+    /// Part 1: move single blocks into the leftmost free block.
+    Blocks,
+    /// Part 2: move whole files into the leftmost gap that fits.
+    Files,
+    /// Move whole files into the smallest gap that fits.
+    BestFit,
+    /// Pack all files to the start of the disk in their current order.
+    Defragment,
+}
+
+impl FromStr for Strategy {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(match s {
+            "blocks" => Strategy::Blocks,
+            "files" => Strategy::Files,
+            "best-fit" => Strategy::BestFit,
+            "defragment" => Strategy::Defragment,
+            _ => bail!("Unknown compaction strategy '{}'", s),
+        })
+    }
+}
+
+impl fmt::Display for Strategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Strategy::Blocks => write!(f, "blocks"),
+            Strategy::Files => write!(f, "files"),
+            Strategy::BestFit => write!(f, "best-fit"),
+            Strategy::Defragment => write!(f, "defragment"),
+        }
+    }
+}
+
+impl Strategy {
+    const ALL: [Strategy; 4] = [
+        Strategy::Blocks,
+        Strategy::Files,
+        Strategy::BestFit,
+        Strategy::Defragment,
+    ];
+
+    fn compact(&self, disk_map: &mut Vec<Chunk>, max_id: usize) {
+        match self {
+            Strategy::Blocks => compact_v1(disk_map),
+            Strategy::Files => compact_v2(disk_map, max_id),
+            Strategy::BestFit => compact_best_fit(disk_map, max_id),
+            Strategy::Defragment => defragment(disk_map),
+        }
+    }
+}
+
+/// Moves individual blocks from the end of the disk into the leftmost free blocks.
+fn compact_v1(disk_map: &mut Vec<Chunk>) {
+    let mut chunks = disk_map.clone();
+    let total_len: usize = chunks.iter().map(|c| c.len).sum();
+    let mut compacted: Vec<Chunk> = Vec::with_capacity(chunks.len());
+    let mut front_idx = 0;
+    let mut back_idx = chunks.len() - 1;
+    while front_idx <= back_idx {
+        let front_chunk = chunks[front_idx];
+        if front_chunk.chunk_type != ChunkType::Empty {
+            // Chunk is full. Move on.
+            compacted.push(front_chunk);
+            front_idx += 1;
+            continue;
+        }
+        let back_chunk = chunks[back_idx];
+        if back_chunk.chunk_type == ChunkType::Empty || back_chunk.len == 0 {
+            if back_idx == front_idx {
+                break;
+            }
+            back_idx -= 1;
+            continue;
+        }
+        // Fill as much of the front gap as the back chunk allows
+        let moved = front_chunk.len.min(back_chunk.len);
+        if moved > 0 {
+            compacted.push(Chunk {
+                len: moved,
+                chunk_type: back_chunk.chunk_type,
+            });
+        }
+        chunks[front_idx].len -= moved;
+        chunks[back_idx].len -= moved;
+        if chunks[front_idx].len == 0 {
+            front_idx += 1;
+        }
+    }
+    let used: usize = compacted.iter().map(|c| c.len).sum();
+    push_gap(&mut compacted, total_len - used);
+    *disk_map = compacted;
+}
+
+/// Free spans that files can be moved into.
+trait GapIndex {
+    /// Builds the index from (position, length) of every free span, in disk order.
+    fn new(spans: &[(usize, usize)]) -> Self;
+
+    /// Claims `len` blocks from the chosen gap starting before `before`.
+    /// Returns the position of the claimed blocks, or None if no gap qualifies.
+    /// Files are moved right to left, so `before` never increases between calls.
+    fn allocate(&mut self, len: usize, before: usize) -> Option<usize>;
+}
+
+/// Chooses the leftmost gap that fits.
+///
+/// Gaps stay in disk order in a max segment tree over their remaining lengths, so the leftmost
+/// gap of at least a given length is found in O(log n).
+struct FirstFit {
+    positions: Vec<usize>,
+    /// Node `n` holds the longest gap among its children `2n` and `2n + 1`; leaves start at `size`.
+    tree: Vec<usize>,
+    size: usize,
+}
+
+impl FirstFit {
+    fn set_len(&mut self, slot: usize, len: usize) {
+        let mut node = self.size + slot;
+        self.tree[node] = len;
+        while node > 1 {
+            node /= 2;
+            self.tree[node] = self.tree[2 * node].max(self.tree[2 * node + 1]);
+        }
+    }
+}
+
+impl GapIndex for FirstFit {
+    fn new(spans: &[(usize, usize)]) -> Self {
+        let size = spans.len().next_power_of_two();
+        let mut tree = vec![0; 2 * size];
+        for (slot, &(_, len)) in spans.iter().enumerate() {
+            tree[size + slot] = len;
+        }
+        for node in (1..size).rev() {
+            tree[node] = tree[2 * node].max(tree[2 * node + 1]);
+        }
+        Self {
+            positions: spans.iter().map(|&(position, _)| position).collect(),
+            tree,
+            size,
+        }
+    }
+
+    fn allocate(&mut self, len: usize, before: usize) -> Option<usize> {
+        if self.positions.is_empty() || self.tree[1] < len {
+            return None;
+        }
+        let mut node = 1;
+        while node < self.size {
+            node = match self.tree[2 * node] >= len {
+                true => 2 * node,
+                false => 2 * node + 1,
+            };
+        }
+        let slot = node - self.size;
+        let position = self.positions[slot];
+        if position >= before {
+            return None;
+        }
+        // The rest of the gap keeps its place in disk order
+        self.positions[slot] += len;
+        self.set_len(slot, self.tree[node] - len);
+        Some(position)
+    }
+}
+
+/// Chooses the smallest gap that fits, leftmost among equals.
+///
+/// Gaps are kept in one min-heap of start positions per length, so the leftmost gap of each
+/// length is always at the head of its heap.
+struct BestFit(BTreeMap<usize, BinaryHeap<Reverse<usize>>>);
+
+impl GapIndex for BestFit {
+    fn new(spans: &[(usize, usize)]) -> Self {
+        let mut gaps: BTreeMap<usize, BinaryHeap<Reverse<usize>>> = BTreeMap::new();
+        for &(position, len) in spans {
+            gaps.entry(len).or_default().push(Reverse(position));
+        }
+        Self(gaps)
+    }
+
+    fn allocate(&mut self, len: usize, before: usize) -> Option<usize> {
+        let mut gap_len = None;
+        let mut unreachable = vec![];
+        for (&candidate, heap) in self.0.range(len..) {
+            match heap.peek() {
+                Some(&Reverse(position)) if position < before => {
+                    gap_len = Some(candidate);
+                    break;
+                }
+                // Every gap of this length lies at or after `before`, and will stay out of reach.
+                _ => unreachable.push(candidate),
+            }
+        }
+        for candidate in unreachable {
+            self.0.remove(&candidate);
+        }
+        let gap_len = gap_len?;
+        let heap = self.0.get_mut(&gap_len).unwrap();
+        let Reverse(position) = heap.pop().unwrap();
+        if heap.is_empty() {
+            self.0.remove(&gap_len);
+        }
+        if gap_len > len {
+            self.0
+                .entry(gap_len - len)
+                .or_default()
+                .push(Reverse(position + len));
+        }
+        Some(position)
+    }
+}
+
+/// Moves whole files, highest id first, into the gap chosen by `G`.
+fn move_files<G: GapIndex>(disk_map: &mut Vec<Chunk>, max_id: usize) {
+    // Start position and length of each file, indexed by id
+    let mut files: Vec<(usize, usize)> = vec![(0, 0); max_id + 1];
+    let mut spans: Vec<(usize, usize)> = vec![];
+    let mut position = 0;
+    for chunk in disk_map.iter() {
+        match chunk.chunk_type {
+            ChunkType::Full(id) => files[id] = (position, chunk.len),
+            ChunkType::Empty if chunk.len > 0 => spans.push((position, chunk.len)),
+            ChunkType::Empty => {}
+        }
+        position += chunk.len;
+    }
+    let total_len = position;
+    let mut gaps = G::new(&spans);
+    for id in (0..max_id + 1).rev() {
+        let (file_position, len) = files[id];
+        // An empty file takes no space, and placing it inside a gap would leave it sharing a
+        // position with whichever file fills that gap later.
+        if len == 0 {
+            continue;
+        }
+        // The file's old location is never reused: only files further left remain to be moved.
+        if let Some(gap_position) = gaps.allocate(len, file_position) {
+            files[id].0 = gap_position;
+        }
+    }
+    *disk_map = layout(&files, total_len);
+}
+
+/// Moves whole files, highest id first, into the leftmost gap that fits them.
+fn compact_v2(disk_map: &mut Vec<Chunk>, max_id: usize) {
+    move_files::<FirstFit>(disk_map, max_id);
+}
+
+/// Moves whole files, highest id first, into the smallest gap that fits them.
+fn compact_best_fit(disk_map: &mut Vec<Chunk>, max_id: usize) {
+    move_files::<BestFit>(disk_map, max_id);
+}
+
+/// Slides every file left, keeping their order, so all free space ends up at the end of the disk.
+fn defragment(disk_map: &mut Vec<Chunk>) {
+    let total_len: usize = disk_map.iter().map(|c| c.len).sum();
+    let mut packed: Vec<Chunk> = disk_map
+        .iter()
+        .filter(|c| c.chunk_type != ChunkType::Empty)
+        .copied()
+        .collect();
+    let used: usize = packed.iter().map(|c| c.len).sum();
+    push_gap(&mut packed, total_len - used);
+    *disk_map = packed;
+}
+
+/// Appends `len` free blocks, if there are any.
+fn push_gap(disk_map: &mut Vec<Chunk>, len: usize) {
+    if len > 0 {
+        disk_map.push(Chunk {
+            len,
+            chunk_type: ChunkType::Empty,
+        });
+    }
+}
+
+/// Rebuilds a disk map from the position and length of every file, filling the rest with gaps.
+fn layout(files: &[(usize, usize)], total_len: usize) -> Vec<Chunk> {
+    let mut order: Vec<usize> = (0..files.len()).collect();
+    order.sort_by_key(|&id| files[id].0);
+    let mut disk_map: Vec<Chunk> = Vec::with_capacity(files.len() * 2);
+    let mut position = 0;
+    for id in order {
+        let (file_position, len) = files[id];
+        push_gap(&mut disk_map, file_position - position);
+        disk_map.push(Chunk {
+            len,
+            chunk_type: ChunkType::Full(id),
+        });
+        position = file_position + len;
+    }
+    push_gap(&mut disk_map, total_len - position);
+    disk_map
+}
+
+/// Parses a disk map into chunks, returning them with the highest file id.
+fn parse(contents: &str, extended: bool) -> Result<(Vec<Chunk>, usize)> {
+    // The extended format lists lengths of any size separated by commas, e.g. `12,0,305`.
+    let lengths: Vec<usize> = match extended || contents.contains(',') {
+        true => contents
+            .split(',')
+            .map(|t| t.trim().parse())
+            .try_collect()?,
+        false => contents
+            .chars()
+            .map(|c| {
+                c.to_digit(10)
+                    .map(|d| d as usize)
+                    .ok_or_else(|| anyhow!("Unexpected character '{}' in disk map", c))
+            })
+            .try_collect()?,
+    };
+    let mut disk_map: Vec<Chunk> = vec![];
+    let mut id = 0;
+    for (i, len) in lengths.into_iter().enumerate() {
+        let chunk = Chunk {
+            len,
+            chunk_type: match i % 2 == 0 {
+                true => {
+                    let new_id = id;
+                    id += 1;
+                    ChunkType::Full(new_id)
+                }
+                false => ChunkType::Empty,
+            },
+        };
+        disk_map.push(chunk);
+    }
+    Ok((disk_map, id - 1))
+}
+
+pub fn day09(input_path: &Path, options: &Options) -> Result<(String, String)> {
+    let contents: String = read_to_string(input_path)
+        .expect("Error reading file")
+        .trim()
+        .to_string();
+    let (disk_map, max_id) = parse(&contents, options.flag("extended"))?;
+    let strategies: Vec<Strategy> = match options.get_str("strategies") {
+        None => vec![],
+        Some("all") => Strategy::ALL.to_vec(),
+        Some(names) => names.split(',').map(|n| n.parse()).try_collect()?,
+    };
+    let (render_layout, show_stats) = (options.flag("render"), options.flag("stats"));
+    if render_layout {
+        println!("initial: {}", render(&disk_map));
+    }
+    if show_stats {
+        println!("initial: {}", fragmentation_stats(&disk_map));
+    }
+    for strategy in strategies {
+        let mut compacted = disk_map.clone();
+        strategy.compact(&mut compacted, max_id);
+        println!("{}: checksum {}", strategy, checksum(&compacted)?);
+        if render_layout {
+            println!("{}: {}", strategy, render(&compacted));
+        }
+        if show_stats {
+            println!("{}: {}", strategy, fragmentation_stats(&compacted));
+        }
+    }
+    let mut disk_map_1 = disk_map.clone();
+    let mut disk_map_2 = disk_map;
+    Strategy::Blocks.compact(&mut disk_map_1, max_id);
+    Strategy::Files.compact(&mut disk_map_2, max_id);
+    let p1 = checksum(&disk_map_1)?;
+    let p2 = checksum(&disk_map_2)?;
+
+    Ok((p1.to_string(), p2.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn checksums(contents: &str) -> Vec<(Strategy, usize)> {
+        let (disk_map, max_id) = parse(contents, false).unwrap();
+        Strategy::ALL
+            .iter()
+            .map(|&strategy| {
+                let mut compacted = disk_map.clone();
+                strategy.compact(&mut compacted, max_id);
+                (strategy, checksum(&compacted).unwrap())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn example() {
+        let sums = checksums("2333133121414131402");
+        assert_eq!(sums[0], (Strategy::Blocks, 1928));
+        assert_eq!(sums[1], (Strategy::Files, 2858));
+    }
+
+    #[test]
+    fn zero_length_files_stay_put() {
+        for (strategy, sum) in checksums("12100") {
+            assert_eq!(sum, 1, "{}", strategy);
+        }
+    }
+}
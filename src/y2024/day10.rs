@@ -0,0 +1,193 @@
+use std::collections::{HashMap, HashSet};
+use std::fs::read_to_string;
+use std::path::Path;
+
+use anyhow::{bail, Result};
+use itertools::Itertools;
+
+use crate::coord::{Coord, ORTHOGONAL_DIRECTIONS};
+use crate::options::Options;
+
+const PEAK: u8 = 9;
+
+/// Heights of the passable cells. Cells marked `.` are impassable and have no height.
+struct TopoMap {
+    heights: HashMap<Coord, u8>,
+    rows: isize,
+    cols: isize,
+}
+
+/// Per-cell trail counts, computed one height at a time.
+struct TrailCounts {
+    /// Number of trails from the cell up to any peak.
+    up: HashMap<Coord, u64>,
+    /// Number of trails from any trailhead up to the cell.
+    down: HashMap<Coord, u64>,
+    /// Number of distinct peaks reachable from each trailhead.
+    peaks_reachable: HashMap<Coord, usize>,
+}
+
+impl TopoMap {
+    fn parse(contents: &str) -> Result<Self> {
+        let mut heights: HashMap<Coord, u8> = HashMap::new();
+        let (mut rows, mut cols) = (0, 0);
+        for (i, line) in contents.split("\n").enumerate() {
+            rows = rows.max(i as isize + 1);
+            for (j, c) in line.char_indices() {
+                cols = cols.max(j as isize + 1);
+                match c {
+                    '.' => {}
+                    '0'..='9' => _ = heights.insert(Coord(i as isize, j as isize), c as u8 - b'0'),
+                    _ => bail!(
+                        "Invalid map cell '{}' at line {}, column {}",
+                        c,
+                        i + 1,
+                        j + 1
+                    ),
+                }
+            }
+        }
+        Ok(Self {
+            heights,
+            rows,
+            cols,
+        })
+    }
+
+    /// Neighbours one step higher than `cell`.
+    fn uphill(&self, cell: Coord) -> impl Iterator<Item = Coord> + '_ {
+        let height = self.heights[&cell];
+        ORTHOGONAL_DIRECTIONS
+            .into_iter()
+            .map(move |d| cell + d)
+            .filter(move |n| self.heights.get(n) == Some(&(height + 1)))
+    }
+
+    /// Neighbours one step lower than `cell`.
+    fn downhill(&self, cell: Coord) -> impl Iterator<Item = Coord> + '_ {
+        let height = self.heights[&cell];
+        ORTHOGONAL_DIRECTIONS
+            .into_iter()
+            .map(move |d| cell + d)
+            .filter(move |n| height > 0 && self.heights.get(n) == Some(&(height - 1)))
+    }
+
+    fn cells_at(&self, height: u8) -> Vec<Coord> {
+        self.heights
+            .iter()
+            .filter(|&(_, &h)| h == height)
+            .map(|(&c, _)| c)
+            .collect()
+    }
+
+    fn trailheads(&self) -> Vec<Coord> {
+        self.cells_at(0)
+            .into_iter()
+            .sorted_by_key(|c| (c.0, c.1))
+            .collect()
+    }
+
+    /// Dynamic programming over heights: a cell's counts only depend on the cells one step higher
+    /// (or lower), so each cell is visited once per direction. Only the sets of reachable peaks for
+    /// the current and next height are kept in memory.
+    fn trail_counts(&self) -> TrailCounts {
+        let mut up: HashMap<Coord, u64> = HashMap::new();
+        let mut peaks: HashMap<Coord, HashSet<Coord>> = HashMap::new();
+        for height in (0..=PEAK).rev() {
+            let mut next_peaks: HashMap<Coord, HashSet<Coord>> = HashMap::new();
+            for cell in self.cells_at(height) {
+                let (count, reachable) = match height {
+                    PEAK => (1, HashSet::from([cell])),
+                    _ => self
+                        .uphill(cell)
+                        .fold((0, HashSet::new()), |(n, mut r), next| {
+                            r.extend(&peaks[&next]);
+                            (n + up[&next], r)
+                        }),
+                };
+                up.insert(cell, count);
+                next_peaks.insert(cell, reachable);
+            }
+            peaks = next_peaks;
+        }
+        let peaks_reachable = peaks.into_iter().map(|(c, r)| (c, r.len())).collect();
+        let mut down: HashMap<Coord, u64> = HashMap::new();
+        for height in 0..=PEAK {
+            for cell in self.cells_at(height) {
+                let count = match height {
+                    0 => 1,
+                    _ => self.downhill(cell).map(|prev| down[&prev]).sum(),
+                };
+                down.insert(cell, count);
+            }
+        }
+        TrailCounts {
+            up,
+            down,
+            peaks_reachable,
+        }
+    }
+
+    /// Every trail from `start` to a peak, as the cells it passes through.
+    fn trails_from(&self, start: Coord) -> Vec<Vec<Coord>> {
+        let mut trails = vec![];
+        let mut stack: Vec<Vec<Coord>> = vec![vec![start]];
+        while let Some(trail) = stack.pop() {
+            let last = *trail.last().unwrap();
+            if self.heights[&last] == PEAK {
+                trails.push(trail);
+                continue;
+            }
+            for next in self.uphill(last) {
+                let mut extended = trail.clone();
+                extended.push(next);
+                stack.push(extended);
+            }
+        }
+        trails
+    }
+
+    /// Number of trails passing through each cell, right-aligned in columns. Impassable cells
+    /// are drawn as `.`.
+    fn render_heatmap(&self, counts: &TrailCounts) -> String {
+        let through = |c: &Coord| match self.heights.contains_key(c) {
+            true => (counts.up[c] * counts.down[c]).to_string(),
+            false => ".".to_string(),
+        };
+        let width = self
+            .heights
+            .keys()
+            .map(|c| through(c).len())
+            .max()
+            .unwrap_or(1);
+        let mut rendered = String::new();
+        for i in 0..self.rows {
+            let row = (0..self.cols)
+                .map(|j| format!("{:>width$}", through(&Coord(i, j)), width = width))
+                .join(" ");
+            rendered.push_str(&row);
+            rendered.push('\n');
+        }
+        rendered
+    }
+}
+
+pub fn day10(input_path: &Path, options: &Options) -> Result<(String, String)> {
+    let contents: String = read_to_string(input_path).expect("Error reading file");
+    let map = TopoMap::parse(&contents)?;
+    let counts = map.trail_counts();
+    let trailheads = map.trailheads();
+    let p1: usize = trailheads.iter().map(|t| counts.peaks_reachable[t]).sum();
+    let p2: u64 = trailheads.iter().map(|t| counts.up[t]).sum();
+    if options.flag("trails") {
+        for &trailhead in &trailheads {
+            for trail in map.trails_from(trailhead) {
+                println!("{}", trail.iter().join(" -> "));
+            }
+        }
+    }
+    if options.flag("heatmap") {
+        print!("{}", map.render_heatmap(&counts));
+    }
+    Ok((p1.to_string(), p2.to_string()))
+}
@@ -0,0 +1,348 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs::read_to_string;
+use std::path::Path;
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, Result};
+use itertools::Itertools;
+
+use crate::arith::{self, OverflowError};
+use crate::options::Options;
+
+/// Day reported in arithmetic overflow errors.
+const DAY: u8 = 11;
+
+/// The puzzle's rules, in the format accepted by `--rules`.
+const DEFAULT_RULES: &str = "0 -> 1; even-digits -> split; any -> mul 2024";
+
+#[derive(Clone, Copy, Debug)]
+enum Condition {
+    Equals(u64),
+    EvenDigits,
+    OddDigits,
+    Any,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Action {
+    Replace(u64),
+    /// Split the stone's digits into a left and a right half.
+    Split,
+    Mul(u64),
+    Add(u64),
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Rule {
+    condition: Condition,
+    action: Action,
+}
+
+/// Ordered transformation rules. The first rule whose condition matches a stone is applied;
+/// stones matching no rule are left unchanged.
+#[derive(Clone, Debug)]
+pub struct RuleTable(Vec<Rule>);
+
+fn digits(stone: u64) -> u32 {
+    stone.checked_ilog10().unwrap_or(0) + 1
+}
+
+impl FromStr for Condition {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(match s {
+            "even-digits" => Condition::EvenDigits,
+            "odd-digits" => Condition::OddDigits,
+            "any" => Condition::Any,
+            _ => Condition::Equals(
+                s.parse()
+                    .map_err(|_| anyhow!("Unknown condition '{}'", s))?,
+            ),
+        })
+    }
+}
+
+impl FromStr for Action {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let tokens: Vec<&str> = s.split_whitespace().collect();
+        let operand = |t: &str| {
+            t.parse::<u64>()
+                .map_err(|_| anyhow!("Invalid operand '{}' in action '{}'", t, s))
+        };
+        Ok(match tokens.as_slice() {
+            ["split"] => Action::Split,
+            ["mul", n] => Action::Mul(operand(n)?),
+            ["add", n] => Action::Add(operand(n)?),
+            [n] => Action::Replace(operand(n)?),
+            _ => bail!("Unknown action '{}'", s),
+        })
+    }
+}
+
+impl FromStr for RuleTable {
+    type Err = anyhow::Error;
+
+    /// Parses rules of the form `<condition> -> <action>` separated by `;`.
+    /// Conditions: a stone number, `even-digits`, `odd-digits` or `any`.
+    /// Actions: a stone number, `split`, `mul <n>` or `add <n>`.
+    fn from_str(s: &str) -> Result<Self> {
+        let rules: Vec<Rule> = s
+            .split(';')
+            .filter(|r| !r.trim().is_empty())
+            .map(|r| {
+                let (condition, action) = r
+                    .split_once("->")
+                    .ok_or_else(|| anyhow!("Rule '{}' is missing '->'", r.trim()))?;
+                Ok::<Rule, anyhow::Error>(Rule {
+                    condition: condition.trim().parse()?,
+                    action: action.trim().parse()?,
+                })
+            })
+            .try_collect()?;
+        if rules.is_empty() {
+            bail!("Rule table is empty");
+        }
+        Ok(Self(rules))
+    }
+}
+
+impl Default for RuleTable {
+    fn default() -> Self {
+        DEFAULT_RULES.parse().unwrap()
+    }
+}
+
+impl RuleTable {
+    /// Returns the stone(s) the given stone turns into after one blink.
+    fn apply(&self, stone: u64) -> Result<(u64, Option<u64>), OverflowError> {
+        let Some(rule) = self.0.iter().find(|rule| match rule.condition {
+            Condition::Equals(n) => stone == n,
+            Condition::EvenDigits => digits(stone).is_multiple_of(2),
+            Condition::OddDigits => !digits(stone).is_multiple_of(2),
+            Condition::Any => true,
+        }) else {
+            return Ok((stone, None));
+        };
+        Ok(match rule.action {
+            Action::Replace(n) => (n, None),
+            Action::Split => {
+                let half = 10_u64.pow(digits(stone) / 2);
+                (stone / half, Some(stone % half))
+            }
+            Action::Mul(n) => (arith::mul(DAY, stone, n)?, None),
+            Action::Add(n) => (arith::add(DAY, stone, n)?, None),
+        })
+    }
+}
+
+/// Number of stones sharing one engraving.
+trait Count: Clone + fmt::Display {
+    fn zero() -> Self;
+    fn one() -> Self;
+    /// Adds `rhs` in place. Returns false if the count no longer fits.
+    fn add(&mut self, rhs: &Self) -> bool;
+}
+
+impl Count for u64 {
+    fn zero() -> Self {
+        0
+    }
+
+    fn one() -> Self {
+        1
+    }
+
+    fn add(&mut self, rhs: &Self) -> bool {
+        match self.checked_add(*rhs) {
+            Some(sum) => {
+                *self = sum;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// Unbounded count, stored as little-endian base 2^64 limbs.
+#[derive(Clone, Debug)]
+struct BigCount(Vec<u64>);
+
+impl Count for BigCount {
+    fn zero() -> Self {
+        Self(vec![])
+    }
+
+    fn one() -> Self {
+        Self(vec![1])
+    }
+
+    fn add(&mut self, rhs: &Self) -> bool {
+        if self.0.len() < rhs.0.len() {
+            self.0.resize(rhs.0.len(), 0);
+        }
+        let mut carry = false;
+        for (i, limb) in self.0.iter_mut().enumerate() {
+            let (sum, c1) = limb.overflowing_add(*rhs.0.get(i).unwrap_or(&0));
+            let (sum, c2) = sum.overflowing_add(carry as u64);
+            *limb = sum;
+            carry = c1 || c2;
+        }
+        if carry {
+            self.0.push(1);
+        }
+        true
+    }
+}
+
+impl fmt::Display for BigCount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const CHUNK: u128 = 10_000_000_000_000_000_000;
+        let mut limbs = self.0.clone();
+        let mut chunks: Vec<u64> = vec![];
+        while limbs.iter().any(|&l| l != 0) {
+            let mut remainder: u128 = 0;
+            for limb in limbs.iter_mut().rev() {
+                let value = (remainder << 64) | *limb as u128;
+                *limb = (value / CHUNK) as u64;
+                remainder = value % CHUNK;
+            }
+            chunks.push(remainder as u64);
+        }
+        match chunks.split_last() {
+            None => write!(f, "0"),
+            Some((most_significant, rest)) => {
+                write!(f, "{}", most_significant)?;
+                for chunk in rest.iter().rev() {
+                    write!(f, "{:019}", chunk)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Stone counts after a blink, plus the number of distinct engravings.
+struct BlinkStats<C> {
+    total: C,
+    distinct: usize,
+}
+
+fn add_stones<C: Count>(counts: &mut HashMap<u64, C>, stone: u64, count: &C) -> bool {
+    match counts.get_mut(&stone) {
+        Some(existing) => existing.add(count),
+        None => {
+            counts.insert(stone, count.clone());
+            true
+        }
+    }
+}
+
+/// Blinks `blinks` times, tracking how many stones carry each engraving rather than every stone.
+/// Returns the stats after each blink, or None if a count no longer fits in `C`.
+fn simulate<C: Count>(
+    stones: &[u64],
+    rules: &RuleTable,
+    blinks: usize,
+) -> Result<Option<Vec<BlinkStats<C>>>, OverflowError> {
+    let mut counts: HashMap<u64, C> = HashMap::new();
+    for &stone in stones {
+        if !add_stones(&mut counts, stone, &C::one()) {
+            return Ok(None);
+        }
+    }
+    let mut transitions: HashMap<u64, (u64, Option<u64>)> = HashMap::new();
+    let mut history: Vec<BlinkStats<C>> = vec![];
+    for _ in 0..blinks {
+        let mut next: HashMap<u64, C> = HashMap::with_capacity(counts.len());
+        for (stone, count) in counts {
+            let (left, right) = match transitions.get(&stone) {
+                Some(&transition) => transition,
+                None => {
+                    let transition = rules.apply(stone)?;
+                    transitions.insert(stone, transition);
+                    transition
+                }
+            };
+            if !add_stones(&mut next, left, &count) {
+                return Ok(None);
+            }
+            if let Some(right) = right {
+                if !add_stones(&mut next, right, &count) {
+                    return Ok(None);
+                }
+            }
+        }
+        counts = next;
+        let mut total = C::zero();
+        for count in counts.values() {
+            if !total.add(count) {
+                return Ok(None);
+            }
+        }
+        history.push(BlinkStats {
+            total,
+            distinct: counts.len(),
+        });
+    }
+    Ok(Some(history))
+}
+
+/// Simulates with `u64` counts, falling back to unbounded counts if they overflow.
+/// Returns the (total, distinct) stone counts after each blink.
+fn simulate_any(
+    stones: &[u64],
+    rules: &RuleTable,
+    blinks: usize,
+) -> Result<Vec<(String, usize)>, OverflowError> {
+    let history = match simulate::<u64>(stones, rules, blinks)? {
+        Some(history) => history
+            .into_iter()
+            .map(|b| (b.total.to_string(), b.distinct))
+            .collect(),
+        None => simulate::<BigCount>(stones, rules, blinks)?
+            .unwrap()
+            .into_iter()
+            .map(|b| (b.total.to_string(), b.distinct))
+            .collect(),
+    };
+    Ok(history)
+}
+
+pub fn day11(input_path: &Path, options: &Options) -> Result<(String, String)> {
+    let blinks_p1: usize = options.get_or("p1-blinks", 25)?;
+    let blinks_p2: usize = options.get_or("p2-blinks", 75)?;
+    let rules: RuleTable = match options.get_str("rules") {
+        Some(rules) => rules.parse()?,
+        None => RuleTable::default(),
+    };
+    let contents: String = read_to_string(input_path).expect("Error reading file");
+    let stones: Vec<u64> = contents
+        .split_whitespace()
+        .map(|stone| stone.parse())
+        .try_collect()?;
+    let history = simulate_any(&stones, &rules, blinks_p1.max(blinks_p2))?;
+    if options.flag("stats") {
+        println!(
+            "blink 0: {} stones, {} distinct",
+            stones.len(),
+            stones.iter().unique().count()
+        );
+        for (blink, (total, distinct)) in history.iter().enumerate() {
+            println!(
+                "blink {}: {} stones, {} distinct",
+                blink + 1,
+                total,
+                distinct
+            );
+        }
+    }
+    let total_after = |blinks: usize| match blinks {
+        0 => stones.len().to_string(),
+        _ => history[blinks - 1].0.clone(),
+    };
+    Ok((total_after(blinks_p1), total_after(blinks_p2)))
+}
@@ -0,0 +1,220 @@
+use std::collections::{HashMap, HashSet};
+use std::fs::{read_to_string, write};
+use std::path::Path;
+
+use anyhow::{bail, Result};
+use itertools::Itertools;
+
+use crate::coord::{Coord, ORTHOGONAL_DIRECTIONS};
+use crate::grid::{label_components, Connectivity};
+use crate::options::Options;
+
+/// A connected area of a single plant type, with the measurements used to price its fence.
+#[derive(Clone, Debug)]
+pub struct Region {
+    /// Position in reading order of each region's first cell
+    pub id: usize,
+    pub plant: char,
+    pub cells: Vec<Coord>,
+    pub area: usize,
+    pub perimeter: usize,
+    pub sides: usize,
+    /// Top left and bottom right corners, inclusive
+    pub bounding_box: (Coord, Coord),
+    /// Number of separate pockets of other plants completely surrounded by this region
+    pub holes: usize,
+    /// Ids of the regions inside those pockets, including nested ones
+    pub enclosed: Vec<usize>,
+}
+
+impl Region {
+    fn new(id: usize, plant: char, cells: Vec<Coord>) -> Self {
+        let min = Coord(
+            cells.iter().map(|c| c.0).min().unwrap(),
+            cells.iter().map(|c| c.1).min().unwrap(),
+        );
+        let max = Coord(
+            cells.iter().map(|c| c.0).max().unwrap(),
+            cells.iter().map(|c| c.1).max().unwrap(),
+        );
+        Self {
+            id,
+            plant,
+            area: cells.len(),
+            perimeter: get_perimeter(&cells),
+            sides: get_sides(&cells),
+            bounding_box: (min, max),
+            holes: 0,
+            enclosed: vec![],
+            cells,
+        }
+    }
+
+    /// Cells inside the bounding box that cannot reach the outside without crossing the region,
+    /// grouped into holes. Since regions are joined orthogonally, the outside may slip between
+    /// two diagonally touching cells, so it is labeled with eight-way connectivity.
+    fn find_holes(&self) -> Vec<Vec<Coord>> {
+        let cells: HashSet<Coord> = self.cells.iter().copied().collect();
+        let (min, max) = self.bounding_box;
+        let (min, max) = (min - Coord(1, 1), max + Coord(1, 1));
+        let outside: HashMap<Coord, bool> = (min.0..=max.0)
+            .flat_map(|i| (min.1..=max.1).map(move |j| Coord(i, j)))
+            .filter(|c| !cells.contains(c))
+            .map(|c| (c, true))
+            .collect();
+        // The expanded box's corner is always outside the region, and comes first in reading order
+        label_components(&outside, Connectivity::Eight, |a, b| a == b)
+            .regions
+            .into_iter()
+            .skip(1)
+            .collect()
+    }
+
+    fn to_csv(&self) -> String {
+        let (min, max) = self.bounding_box;
+        format!(
+            "{},{},{},{},{},{},{},{},{},{},{}",
+            self.id,
+            csv_quote(self.plant),
+            self.area,
+            self.perimeter,
+            self.sides,
+            min.0,
+            min.1,
+            max.0,
+            max.1,
+            self.holes,
+            self.enclosed.iter().join(";")
+        )
+    }
+
+    fn to_json(&self) -> String {
+        let (min, max) = self.bounding_box;
+        format!(
+            "{{\"id\":{},\"plant\":{},\"area\":{},\"perimeter\":{},\"sides\":{},\
+             \"bounding_box\":[[{},{}],[{},{}]],\"holes\":{},\"enclosed\":[{}]}}",
+            self.id,
+            json_string(self.plant),
+            self.area,
+            self.perimeter,
+            self.sides,
+            min.0,
+            min.1,
+            max.0,
+            max.1,
+            self.holes,
+            self.enclosed.iter().join(",")
+        )
+    }
+}
+
+/// A plant label as a quoted CSV field, so separators and quotes stay inside it.
+fn csv_quote(plant: char) -> String {
+    match plant {
+        '"' => "\"\"\"\"".to_string(),
+        _ => format!("\"{}\"", plant),
+    }
+}
+
+/// A plant label as a JSON string literal.
+fn json_string(plant: char) -> String {
+    match plant {
+        '"' => "\"\\\"\"".to_string(),
+        '\\' => "\"\\\\\"".to_string(),
+        c if c.is_control() => format!("\"\\u{:04x}\"", c as u32),
+        c => format!("\"{}\"", c),
+    }
+}
+
+fn get_perimeter(region: &[Coord]) -> usize {
+    let positions: HashSet<Coord> = region.iter().copied().collect();
+    let mut perimeter = 0;
+    for &p in region {
+        for direction in ORTHOGONAL_DIRECTIONS {
+            if !positions.contains(&(p + direction)) {
+                perimeter += 1;
+            }
+        }
+    }
+    perimeter
+}
+
+/// A polygon has as many sides as corners. Each cell contributes one corner per quadrant where
+/// both orthogonal neighbours are outside the region (convex), or both are inside but the diagonal
+/// between them is not (concave).
+fn get_sides(region: &[Coord]) -> usize {
+    let positions: HashSet<Coord> = region.iter().copied().collect();
+    let mut corners = 0;
+    for &p in region {
+        for (k, &a) in ORTHOGONAL_DIRECTIONS.iter().enumerate() {
+            let b = ORTHOGONAL_DIRECTIONS[(k + 1) % 4];
+            let has_a = positions.contains(&(p + a));
+            let has_b = positions.contains(&(p + b));
+            let has_diagonal = positions.contains(&(p + a + b));
+            if (!has_a && !has_b) || (has_a && has_b && !has_diagonal) {
+                corners += 1;
+            }
+        }
+    }
+    corners
+}
+
+/// Every region in the garden, ordered by their first cell in reading order.
+fn find_regions(contents: &str) -> Vec<Region> {
+    let mut grid: HashMap<Coord, char> = HashMap::new();
+    for (i, line) in contents.split('\n').enumerate() {
+        for (j, c) in line.char_indices() {
+            grid.insert(Coord(i as isize, j as isize), c);
+        }
+    }
+    let components = label_components(&grid, Connectivity::Four, |a, b| a == b);
+    let mut regions: Vec<Region> = components
+        .regions
+        .into_iter()
+        .enumerate()
+        .map(|(id, cells)| Region::new(id, grid[&cells[0]], cells))
+        .collect();
+    for region in regions.iter_mut() {
+        let holes = region.find_holes();
+        region.holes = holes.len();
+        region.enclosed = holes
+            .iter()
+            .flatten()
+            .filter_map(|c| components.labels.get(c).copied())
+            .unique()
+            .sorted()
+            .collect();
+    }
+    regions
+}
+
+pub fn day12(input_path: &Path, options: &Options) -> Result<(String, String)> {
+    let contents: String = read_to_string(input_path).expect("Error reading file");
+    let regions = find_regions(&contents);
+    let p1: usize = regions.iter().map(|r| r.area * r.perimeter).sum();
+    let p2: usize = regions.iter().map(|r| r.area * r.sides).sum();
+    let export = match options.get_str("export") {
+        None => None,
+        Some("csv") => Some(
+            "id,plant,area,perimeter,sides,min_i,min_j,max_i,max_j,holes,enclosed\n".to_string()
+                + &regions
+                    .iter()
+                    .map(|r| r.to_csv() + "\n")
+                    .collect::<String>(),
+        ),
+        Some("json") => Some(format!(
+            "[{}]\n",
+            regions.iter().map(|r| r.to_json()).join(",")
+        )),
+        Some(format) => bail!(
+            "Unknown region export format '{}', expected csv or json",
+            format
+        ),
+    };
+    match (export, options.get_str("export-file")) {
+        (Some(export), Some(file)) => write(file, export)?,
+        (Some(export), None) => print!("{}", export),
+        (None, _) => {}
+    }
+    Ok((p1.to_string(), p2.to_string()))
+}
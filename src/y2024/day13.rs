@@ -0,0 +1,216 @@
+use std::fs::read_to_string;
+use std::path::Path;
+
+use anyhow::Result;
+
+use crate::arith::{self, OverflowError};
+use crate::matrix::Matrix2x2;
+use crate::options::Options;
+
+/// Day reported in arithmetic overflow errors.
+const DAY: u8 = 13;
+
+#[derive(Clone, Copy, Debug)]
+pub struct ClawConfig {
+    /// Tokens spent per press of button A.
+    pub a_cost: i64,
+    /// Tokens spent per press of button B.
+    pub b_cost: i64,
+    /// Added to both prize coordinates in part 2.
+    pub offset: i64,
+}
+
+impl Default for ClawConfig {
+    fn default() -> Self {
+        Self {
+            a_cost: 3,
+            b_cost: 1,
+            offset: 10000000000000,
+        }
+    }
+}
+
+impl ClawConfig {
+    pub fn from_options(options: &Options) -> Result<Self> {
+        let default = Self::default();
+        Ok(Self {
+            a_cost: options.get_or("a-cost", default.a_cost)?,
+            b_cost: options.get_or("b-cost", default.b_cost)?,
+            offset: options.get_or("offset", default.offset)?,
+        })
+    }
+}
+
+fn read_line(re: &regex::Regex, line: &str) -> (i64, i64) {
+    let line_0 = re.captures(line).unwrap();
+    let a = line_0.get(1).unwrap().as_str().parse().unwrap();
+    let b = line_0.get(2).unwrap().as_str().parse().unwrap();
+    (a, b)
+}
+
+/// Returns (g, x, y) such that a * x + b * y = g, with g >= 0.
+fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    if b == 0 {
+        return match a < 0 {
+            true => (-a, -1, 0),
+            false => (a, 1, 0),
+        };
+    }
+    let (g, x, y) = extended_gcd(b, a.rem_euclid(b));
+    (g, y, x - a.div_euclid(b) * y)
+}
+
+fn div_floor(a: i64, b: i64) -> i64 {
+    let q = a / b;
+    match (a % b != 0) && ((a < 0) != (b < 0)) {
+        true => q - 1,
+        false => q,
+    }
+}
+
+fn div_ceil(a: i64, b: i64) -> i64 {
+    let q = a / b;
+    match (a % b != 0) && ((a < 0) == (b < 0)) {
+        true => q + 1,
+        false => q,
+    }
+}
+
+/// Cheapest non-negative (x, y) with u * x + v * y = t, where u and v are not both zero.
+fn cheapest_on_line(
+    u: i64,
+    v: i64,
+    t: i64,
+    config: &ClawConfig,
+) -> Result<Option<usize>, OverflowError> {
+    let (g, x_g, y_g) = extended_gcd(u, v);
+    if t % g != 0 {
+        return Ok(None);
+    }
+    // Every solution is (x0 + k * step_x, y0 - k * step_y) for integer k.
+    let (x0, y0) = (arith::mul(DAY, x_g, t / g)?, arith::mul(DAY, y_g, t / g)?);
+    let (step_x, step_y) = (v / g, u / g);
+    let mut lo: Option<i64> = None;
+    let mut hi: Option<i64> = None;
+    let mut bound = |k: i64, is_lower: bool| match is_lower {
+        true => lo = Some(lo.map_or(k, |lo| lo.max(k))),
+        false => hi = Some(hi.map_or(k, |hi| hi.min(k))),
+    };
+    // x >= 0
+    match step_x.signum() {
+        1 => bound(div_ceil(-x0, step_x), true),
+        -1 => bound(div_floor(-x0, step_x), false),
+        _ if x0 < 0 => return Ok(None),
+        _ => {}
+    }
+    // y >= 0
+    match step_y.signum() {
+        1 => bound(div_floor(y0, step_y), false),
+        -1 => bound(div_ceil(y0, step_y), true),
+        _ if y0 < 0 => return Ok(None),
+        _ => {}
+    }
+    if let (Some(lo), Some(hi)) = (lo, hi) {
+        if lo > hi {
+            return Ok(None);
+        }
+    }
+    // Cost is linear in k, so the cheapest solution sits at one end of the feasible range.
+    let slope = config.a_cost * step_x - config.b_cost * step_y;
+    let k = match (slope.signum(), lo, hi) {
+        (1, Some(lo), _) => lo,
+        (-1, _, Some(hi)) => hi,
+        (0, _, _) => lo.or(hi).unwrap_or(0),
+        _ => return Ok(None),
+    };
+    let x = arith::add(DAY, x0, arith::mul(DAY, k, step_x)?)?;
+    let y = arith::sub(DAY, y0, arith::mul(DAY, k, step_y)?)?;
+    tokens(x, y, config).map(Some)
+}
+
+fn tokens(x: i64, y: i64, config: &ClawConfig) -> Result<usize, OverflowError> {
+    let cost = arith::add(
+        DAY,
+        arith::mul(DAY, config.a_cost, x)?,
+        arith::mul(DAY, config.b_cost, y)?,
+    )?;
+    Ok(cost as usize)
+}
+
+/// Button A moves by (a, c), button B by (b, d) and the prize sits at (i, j).
+/// Returns the fewest tokens needed to reach the prize, if it is reachable at all.
+/// See: https://www.youtube.com/watch?v=jBsC34PxzoM
+fn is_in_linear_space(
+    mat_a: Matrix2x2,
+    i: i64,
+    j: i64,
+    config: &ClawConfig,
+) -> Result<Option<usize>, OverflowError> {
+    let det_a = mat_a.try_det(DAY)?;
+    if det_a == 0 {
+        return collinear_tokens(mat_a, i, j, config);
+    }
+    let det_ax = Matrix2x2::new(i, mat_a.b, j, mat_a.d).try_det(DAY)?;
+    let det_ay = Matrix2x2::new(mat_a.a, i, mat_a.c, j).try_det(DAY)?;
+    if det_ax % det_a == 0 && det_ay % det_a == 0 {
+        let x = det_ax / det_a;
+        let y = det_ay / det_a;
+        if x < 0 || y < 0 {
+            return Ok(None);
+        }
+        return tokens(x, y, config).map(Some);
+    }
+    Ok(None)
+}
+
+/// Both buttons move along the same line, so there may be many ways to reach the prize.
+fn collinear_tokens(
+    mat_a: Matrix2x2,
+    i: i64,
+    j: i64,
+    config: &ClawConfig,
+) -> Result<Option<usize>, OverflowError> {
+    let Matrix2x2 { a, b, c, d } = mat_a;
+    if (a, b, c, d) == (0, 0, 0, 0) {
+        return match (i, j) {
+            (0, 0) => Ok(Some(0)),
+            _ => Ok(None),
+        };
+    }
+    // The prize must lie on the buttons' line
+    let cross_a = Matrix2x2::new(a, i, c, j).try_det(DAY)?;
+    let cross_b = Matrix2x2::new(b, i, d, j).try_det(DAY)?;
+    if cross_a != 0 || cross_b != 0 {
+        return Ok(None);
+    }
+    match a != 0 || b != 0 {
+        true => cheapest_on_line(a, b, i, config),
+        false => cheapest_on_line(c, d, j, config),
+    }
+}
+
+pub fn day13(input_path: &Path, options: &Options) -> Result<(String, String)> {
+    let mut p1: usize = 0;
+    let mut p2: usize = 0;
+    let config = ClawConfig::from_options(options)?;
+    let contents: String = read_to_string(input_path).expect("Error reading file");
+    let regex = regex::Regex::new(r".+X.(-?\d+), Y.(-?\d+)$").unwrap();
+    for machine in contents.split("\n\n") {
+        let mut machine_lines = machine.split('\n');
+        let (a, c) = read_line(&regex, machine_lines.next().unwrap());
+        let (b, d) = read_line(&regex, machine_lines.next().unwrap());
+        let (i, j) = read_line(&regex, machine_lines.next().unwrap());
+        let mat_a = Matrix2x2::new(a, b, c, d);
+        if let Some(addend) = is_in_linear_space(mat_a, i, j, &config)? {
+            p1 = arith::add(DAY, p1, addend)?;
+        }
+        let (i, j) = (
+            arith::add(DAY, i, config.offset)?,
+            arith::add(DAY, j, config.offset)?,
+        );
+        if let Some(addend) = is_in_linear_space(mat_a, i, j, &config)? {
+            p2 = arith::add(DAY, p2, addend)?;
+        }
+    }
+    Ok((p1.to_string(), p2.to_string()))
+}
@@ -0,0 +1,742 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
+use std::fs::read_to_string;
+use std::io::{self, BufRead, Write};
+use std::mem;
+use std::path::Path;
+
+use anyhow::{bail, Result};
+use itertools::Itertools;
+
+use crate::coord::{Coord, OrthogonalDirection, RIGHT};
+use crate::options::Options;
+
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+enum GridChar {
+    /// One cell of a box `width` cells wide, `offset` cells right of the box's left edge
+    Box {
+        offset: isize,
+        width: isize,
+    },
+    Empty,
+    Wall,
+}
+
+/// Problems with the puzzle input or the warehouse state. Lines and columns are 1-based positions
+/// in the input file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum WarehouseError {
+    UnexpectedCharacter {
+        line: usize,
+        column: usize,
+        found: char,
+    },
+    /// A `]` without a matching `[`, or a `[` that isn't closed on the same line
+    UnmatchedBracket {
+        line: usize,
+        column: usize,
+    },
+    MissingRobot,
+    MissingInstructions,
+    UnexpectedInstruction {
+        line: usize,
+        column: usize,
+        found: char,
+    },
+    /// The state stopped being consistent after the 0-based instruction `index`.
+    InvalidState {
+        index: usize,
+        line: usize,
+        column: usize,
+        reason: String,
+    },
+}
+
+impl fmt::Display for WarehouseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WarehouseError::UnexpectedCharacter {
+                line,
+                column,
+                found,
+            } => write!(
+                f,
+                "Unexpected character '{}' in map at line {}, column {}",
+                found, line, column
+            ),
+            WarehouseError::UnmatchedBracket { line, column } => write!(
+                f,
+                "Unmatched box bracket in map at line {}, column {}",
+                line, column
+            ),
+            WarehouseError::MissingRobot => write!(f, "The map has no robot '@'"),
+            WarehouseError::MissingInstructions => {
+                write!(
+                    f,
+                    "Expected a blank line between the map and the instructions"
+                )
+            }
+            WarehouseError::UnexpectedInstruction {
+                line,
+                column,
+                found,
+            } => write!(
+                f,
+                "Unexpected instruction '{}' at line {}, column {}",
+                found, line, column
+            ),
+            WarehouseError::InvalidState {
+                index,
+                line,
+                column,
+                reason,
+            } => write!(
+                f,
+                "Invalid state after instruction {} (line {}, column {}): {}",
+                index, line, column, reason
+            ),
+        }
+    }
+}
+
+impl std::error::Error for WarehouseError {}
+
+/// An instruction with its position in the input file.
+struct Instruction {
+    direction: OrthogonalDirection,
+    line: usize,
+    column: usize,
+}
+
+/// Everything needed to take back one step: the robot's previous position and the previous
+/// contents of every cell the step changed.
+struct Move {
+    robot: Coord,
+    changes: Vec<(Coord, GridChar)>,
+}
+
+/// The warehouse state, advanced one instruction at a time.
+pub struct Warehouse {
+    grid: HashMap<Coord, GridChar>,
+    robot: Coord,
+    rows: isize,
+    cols: isize,
+    history: Vec<Move>,
+    /// Cells changed by the step in progress, with their previous contents
+    changes: Vec<(Coord, GridChar)>,
+}
+
+impl Warehouse {
+    /// Parses the map with every tile stretched to `scale` cells wide. Besides the puzzle's `O`
+    /// boxes, which are one tile wide, a box may span several tiles written as `[`, any number
+    /// of `=`, then `]`, so maps can mix box sizes.
+    fn parse(map: &str, scale: isize) -> Result<Self, WarehouseError> {
+        let mut grid: HashMap<Coord, GridChar> = HashMap::new();
+        let mut robot: Option<Coord> = None;
+        let (mut rows, mut cols) = (0, 0);
+        for (i, line) in map.split("\n").enumerate() {
+            rows = rows.max(i as isize + 1);
+            let mut box_start: Option<isize> = None;
+            for (j, c) in line.chars().enumerate() {
+                let coord = Coord(i as isize, scale * j as isize);
+                cols = cols.max(coord.1 + scale);
+                // Inside a box only `=` and the closing `]` may follow, and never outside one
+                if box_start.is_some() != matches!(c, '=' | ']') {
+                    return Err(WarehouseError::UnmatchedBracket {
+                        line: i + 1,
+                        column: j + 1,
+                    });
+                }
+                let (tile, box_width) = match c {
+                    'O' => (GridChar::Empty, Some(scale)),
+                    '[' => {
+                        box_start = Some(coord.1);
+                        (GridChar::Empty, None)
+                    }
+                    '=' => (GridChar::Empty, None),
+                    ']' => {
+                        let start = box_start.take().unwrap();
+                        (GridChar::Empty, Some(coord.1 + scale - start))
+                    }
+                    '.' => (GridChar::Empty, None),
+                    '@' => {
+                        robot = Some(coord);
+                        (GridChar::Empty, None)
+                    }
+                    '#' => (GridChar::Wall, None),
+                    _ => {
+                        return Err(WarehouseError::UnexpectedCharacter {
+                            line: i + 1,
+                            column: j + 1,
+                            found: c,
+                        })
+                    }
+                };
+                for k in 0..scale {
+                    grid.insert(coord + RIGHT * k, tile);
+                }
+                if let Some(width) = box_width {
+                    // The box ends at the current tile
+                    let left = Coord(coord.0, coord.1 + scale - width);
+                    for offset in 0..width {
+                        grid.insert(left + RIGHT * offset, GridChar::Box { offset, width });
+                    }
+                }
+            }
+            if box_start.is_some() {
+                return Err(WarehouseError::UnmatchedBracket {
+                    line: i + 1,
+                    column: line.chars().count(),
+                });
+            }
+        }
+        Ok(Self {
+            grid,
+            robot: robot.ok_or(WarehouseError::MissingRobot)?,
+            rows,
+            cols,
+            history: vec![],
+            changes: vec![],
+        })
+    }
+
+    pub fn robot(&self) -> Coord {
+        self.robot
+    }
+
+    /// Cells beyond the map behave like walls.
+    fn get(&self, coord: Coord) -> GridChar {
+        *self.grid.get(&coord).unwrap_or(&GridChar::Wall)
+    }
+
+    fn set(&mut self, coord: Coord, grid_char: GridChar) {
+        let previous = self.grid.insert(coord, grid_char).unwrap();
+        self.changes.push((coord, previous));
+    }
+
+    /// Left edges and widths of the boxes the robot would push by stepping in `direction`,
+    /// found by a BFS over the cells the boxes would move into. Returns None if any box is
+    /// blocked by a wall.
+    fn boxes_to_push(&self, direction: OrthogonalDirection) -> Option<Vec<(Coord, isize)>> {
+        let step = direction.coord();
+        let mut boxes: Vec<(Coord, isize)> = vec![];
+        let mut seen: HashSet<Coord> = HashSet::new();
+        let mut queue: VecDeque<Coord> = VecDeque::from([self.robot + step]);
+        while let Some(cell) = queue.pop_front() {
+            let (offset, width) = match self.get(cell) {
+                GridChar::Empty => continue,
+                GridChar::Wall => return None,
+                GridChar::Box { offset, width } => (offset, width),
+            };
+            let left = cell - RIGHT * offset;
+            if !seen.insert(left) {
+                continue;
+            }
+            boxes.push((left, width));
+            for k in 0..width {
+                let next = left + RIGHT * k + step;
+                // Moving sideways, a box's own cells don't block it
+                let own = next.0 == left.0 && (left.1..left.1 + width).contains(&next.1);
+                if !own {
+                    queue.push_back(next);
+                }
+            }
+        }
+        Some(boxes)
+    }
+
+    /// Moves the robot one cell, pushing any boxes in the way. Returns whether the robot moved.
+    /// The step is recorded even if nothing moved, so every step can be undone.
+    pub fn step(&mut self, direction: OrthogonalDirection) -> bool {
+        let robot = self.robot;
+        let boxes = self.boxes_to_push(direction);
+        if let Some(boxes) = &boxes {
+            let step = direction.coord();
+            for &(left, width) in boxes {
+                for k in 0..width {
+                    self.set(left + RIGHT * k, GridChar::Empty);
+                }
+            }
+            for &(left, width) in boxes {
+                for offset in 0..width {
+                    self.set(
+                        left + step + RIGHT * offset,
+                        GridChar::Box { offset, width },
+                    );
+                }
+            }
+            self.robot = robot + step;
+        }
+        let changes = mem::take(&mut self.changes);
+        self.history.push(Move { robot, changes });
+        boxes.is_some()
+    }
+
+    /// Takes back the most recent step. Returns false if there is nothing to undo.
+    pub fn undo(&mut self) -> bool {
+        let Some(last) = self.history.pop() else {
+            return false;
+        };
+        for &(coord, grid_char) in last.changes.iter().rev() {
+            self.grid.insert(coord, grid_char);
+        }
+        self.robot = last.robot;
+        true
+    }
+
+    /// The map in the puzzle's notation: `O` for boxes one cell wide, `[]` for wider ones with
+    /// `=` filling the inside.
+    pub fn render(&self) -> String {
+        let mut rendered = String::new();
+        for i in 0..self.rows {
+            for j in 0..self.cols {
+                let coord = Coord(i, j);
+                rendered.push(match self.grid.get(&coord) {
+                    _ if coord == self.robot => '@',
+                    Some(GridChar::Box { width: 1, .. }) => 'O',
+                    Some(GridChar::Box { offset: 0, .. }) => '[',
+                    Some(&GridChar::Box { offset, width }) if offset == width - 1 => ']',
+                    Some(GridChar::Box { .. }) => '=',
+                    Some(GridChar::Wall) => '#',
+                    Some(GridChar::Empty) | None => '.',
+                });
+            }
+            rendered.push('\n');
+        }
+        rendered
+    }
+
+    fn gps(&self) -> usize {
+        calc_gps(&self.grid)
+    }
+
+    /// Looks for the first inconsistency: a box whose cells don't line up, or the robot standing
+    /// on something other than an empty cell.
+    fn check(&self) -> Result<(), String> {
+        match self.get(self.robot) {
+            GridChar::Empty => {}
+            GridChar::Wall => return Err(format!("robot at {} is inside a wall", self.robot)),
+            GridChar::Box { .. } => return Err(format!("robot at {} is inside a box", self.robot)),
+        }
+        for (&coord, &grid_char) in self.grid.iter().sorted_by_key(|(c, _)| (c.0, c.1)) {
+            let GridChar::Box { offset, width } = grid_char else {
+                continue;
+            };
+            if offset < 0 || offset >= width {
+                return Err(format!("box cell at {} is outside its box", coord));
+            }
+            let left = coord - RIGHT * offset;
+            for k in 0..width {
+                let expected = GridChar::Box { offset: k, width };
+                if self.get(left + RIGHT * k) != expected {
+                    return Err(format!(
+                        "box at {} is missing its cell at {}",
+                        left,
+                        left + RIGHT * k
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Sum of the GPS coordinates of each box's left edge.
+fn calc_gps(grid: &HashMap<Coord, GridChar>) -> usize {
+    let mut gps = 0;
+    for (&coord, &grid_char) in grid {
+        if let GridChar::Box { offset: 0, .. } = grid_char {
+            gps += coord.0 as usize * 100 + coord.1 as usize;
+        }
+    }
+    gps
+}
+
+fn parse_direction(instruction: char) -> Option<OrthogonalDirection> {
+    match instruction {
+        '^' => Some(OrthogonalDirection::UP),
+        '>' => Some(OrthogonalDirection::RIGHT),
+        'v' => Some(OrthogonalDirection::DOWN),
+        '<' => Some(OrthogonalDirection::LEFT),
+        _ => None,
+    }
+}
+
+/// Parses the instructions, skipping any whitespace. `first_line` is the instructions' line
+/// number in the input file.
+fn parse_instructions(
+    instructions: &str,
+    first_line: usize,
+) -> Result<Vec<Instruction>, WarehouseError> {
+    let mut parsed = vec![];
+    for (i, line) in instructions.split('\n').enumerate() {
+        for (j, c) in line.chars().enumerate() {
+            if c.is_whitespace() {
+                continue;
+            }
+            let direction = parse_direction(c).ok_or(WarehouseError::UnexpectedInstruction {
+                line: first_line + i,
+                column: j + 1,
+                found: c,
+            })?;
+            parsed.push(Instruction {
+                direction,
+                line: first_line + i,
+                column: j + 1,
+            });
+        }
+    }
+    Ok(parsed)
+}
+
+/// Reads commands from stdin: a string of instructions to step through, `u` to undo a step,
+/// or `q` to quit. Prints the warehouse after every command.
+fn interact(warehouse: &mut Warehouse) -> Result<()> {
+    print!("{}", warehouse.render());
+    let mut stdout = io::stdout();
+    for line in io::stdin().lock().lines() {
+        let line = line?;
+        match line.trim() {
+            "q" => break,
+            "u" => {
+                if !warehouse.undo() {
+                    println!("nothing to undo");
+                }
+            }
+            instructions => {
+                for instruction in instructions.chars().filter(|c| !c.is_whitespace()) {
+                    let Some(direction) = parse_direction(instruction) else {
+                        println!("{}: unknown instruction", instruction);
+                        break;
+                    };
+                    if !warehouse.step(direction) {
+                        println!("{}: blocked", instruction);
+                    }
+                }
+            }
+        }
+        print!("{}", warehouse.render());
+        println!("robot at {}", warehouse.robot());
+        stdout.flush()?;
+    }
+    Ok(())
+}
+
+pub fn day15(input_path: &Path, options: &Options) -> Result<(String, String)> {
+    let contents: String = read_to_string(input_path).expect("Error reading file");
+    let (map, instructions) = contents
+        .split_once("\n\n")
+        .ok_or(WarehouseError::MissingInstructions)?;
+    let instructions = parse_instructions(instructions, map.split('\n').count() + 2)?;
+    let scale_p2: isize = options.get_or("p2-scale", 2)?;
+    if scale_p2 < 1 {
+        bail!("Scale must be at least 1, got {}", scale_p2);
+    }
+    let interactive = match options.get_str("interactive") {
+        None => None,
+        Some("") => Some(1),
+        Some("wide") => Some(scale_p2),
+        Some(scale) => match scale.parse::<isize>() {
+            Ok(scale) if scale >= 1 => Some(scale),
+            _ => bail!(
+                "Unknown warehouse '{}', expected --interactive, --interactive=wide or a scale",
+                scale
+            ),
+        },
+    };
+    if let Some(scale) = interactive {
+        interact(&mut Warehouse::parse(map, scale)?)?;
+    }
+    let validate = options.flag("validate");
+    let mut warehouse = Warehouse::parse(map, 1)?;
+    let mut wide_warehouse = Warehouse::parse(map, scale_p2)?;
+    for (index, instruction) in instructions.iter().enumerate() {
+        for w in [&mut warehouse, &mut wide_warehouse] {
+            w.step(instruction.direction);
+            if validate {
+                w.check().map_err(|reason| WarehouseError::InvalidState {
+                    index,
+                    line: instruction.line,
+                    column: instruction.column,
+                    reason,
+                })?;
+            }
+        }
+    }
+    if options.flag("render") {
+        print!("{}", warehouse.render());
+        print!("{}", wide_warehouse.render());
+    }
+    let p1 = warehouse.gps();
+    let p2 = wide_warehouse.gps();
+    Ok((p1.to_string(), p2.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SMALL: &str = "\
+########
+#..O.O.#
+##@.O..#
+#...O..#
+#.#.O..#
+#...O..#
+#......#
+########
+
+<^^>>>vv<v>>v<<";
+
+    const SMALL_WIDE: &str = "\
+#######
+#...#.#
+#.....#
+#..OO@#
+#..O..#
+#.....#
+#######
+
+<vv<<^^<<^^";
+
+    /// Runs every instruction on the map stretched to `scale`, checking the state after each.
+    fn run(input: &str, scale: isize) -> Warehouse {
+        let (map, instructions) = input.split_once("\n\n").unwrap();
+        let mut warehouse = Warehouse::parse(map, scale).unwrap();
+        for instruction in parse_instructions(instructions, 1).unwrap() {
+            warehouse.step(instruction.direction);
+            warehouse.check().unwrap();
+        }
+        warehouse
+    }
+
+    #[test]
+    fn small_example() {
+        assert_eq!(run(SMALL, 1).gps(), 2028);
+        assert_eq!(run(SMALL, 2).gps(), 1751);
+    }
+
+    #[test]
+    fn small_wide_example() {
+        let warehouse = run(SMALL_WIDE, 2);
+        assert_eq!(
+            warehouse.render(),
+            "\
+##############
+##...[].##..##
+##...@.[]...##
+##....[]....##
+##..........##
+##..........##
+##############
+"
+        );
+        assert_eq!(warehouse.gps(), 618);
+    }
+
+    #[test]
+    fn push_blocked_by_wall() {
+        let mut warehouse = Warehouse::parse("#####\n#@OO#\n#####", 1).unwrap();
+        let before = warehouse.render();
+        assert!(!warehouse.step(OrthogonalDirection::RIGHT));
+        assert_eq!(warehouse.render(), before);
+        // A wide box pushed up is blocked by a wall above either half
+        let mut warehouse = Warehouse::parse("#####\n#.#.#\n#.O@#\n#...#\n#####", 2).unwrap();
+        for direction in [
+            OrthogonalDirection::LEFT,
+            OrthogonalDirection::DOWN,
+            OrthogonalDirection::LEFT,
+        ] {
+            assert!(warehouse.step(direction));
+        }
+        let before = warehouse.render();
+        assert!(!warehouse.step(OrthogonalDirection::UP));
+        assert_eq!(warehouse.render(), before);
+    }
+
+    #[test]
+    fn push_mixed_width_boxes() {
+        let mut warehouse = Warehouse::parse(
+            "\
+#######
+#.....#
+#.O[].#
+#.[=].#
+#..@..#
+#######",
+            1,
+        )
+        .unwrap();
+        assert!(warehouse.step(OrthogonalDirection::UP));
+        let after = "\
+#######
+#.O[].#
+#.[=].#
+#..@..#
+#.....#
+#######
+";
+        assert_eq!(warehouse.render(), after);
+        assert!(!warehouse.step(OrthogonalDirection::UP));
+        assert_eq!(warehouse.render(), after);
+        // Sideways, the three-wide box pushes only what is in its row
+        for direction in [
+            OrthogonalDirection::RIGHT,
+            OrthogonalDirection::RIGHT,
+            OrthogonalDirection::UP,
+            OrthogonalDirection::LEFT,
+        ] {
+            assert!(warehouse.step(direction));
+        }
+        let after = "\
+#######
+#.O[].#
+#[=]@.#
+#.....#
+#.....#
+#######
+";
+        assert_eq!(warehouse.render(), after);
+        assert!(!warehouse.step(OrthogonalDirection::LEFT));
+        assert_eq!(warehouse.render(), after);
+        warehouse.check().unwrap();
+    }
+
+    #[test]
+    fn undo_restores_render() {
+        let (map, instructions) = SMALL.split_once("\n\n").unwrap();
+        let mut warehouse = Warehouse::parse(map, 2).unwrap();
+        let mut renders = vec![warehouse.render()];
+        for instruction in parse_instructions(instructions, 1).unwrap() {
+            warehouse.step(instruction.direction);
+            renders.push(warehouse.render());
+        }
+        while let Some(render) = renders.pop() {
+            assert_eq!(warehouse.render(), render);
+            assert_eq!(warehouse.undo(), !renders.is_empty());
+        }
+    }
+
+    /// The original solver for the puzzle's maps: recursive pushes over the map's characters,
+    /// with `O` boxes at scale 1 and `[]` boxes at scale 2.
+    fn reference_gps(map: &str, instructions: &str, scale: usize) -> usize {
+        let mut grid: Vec<Vec<char>> = map
+            .lines()
+            .map(|line| {
+                line.chars()
+                    .flat_map(|c| match (c, scale) {
+                        ('O', 2) => vec!['[', ']'],
+                        ('@', 2) => vec!['@', '.'],
+                        (c, _) => vec![c; scale],
+                    })
+                    .collect()
+            })
+            .collect();
+        let (mut i, mut j) = grid
+            .iter()
+            .enumerate()
+            .find_map(|(i, row)| Some((i, row.iter().position(|&c| c == '@')?)))
+            .unwrap();
+        fn can_move(grid: &[Vec<char>], (i, j): (usize, usize), (di, dj): (isize, isize)) -> bool {
+            let (ni, nj) = ((i as isize + di) as usize, (j as isize + dj) as usize);
+            match grid[ni][nj] {
+                '#' => false,
+                '[' if di != 0 => {
+                    can_move(grid, (ni, nj), (di, dj)) && can_move(grid, (ni, nj + 1), (di, dj))
+                }
+                ']' if di != 0 => {
+                    can_move(grid, (ni, nj), (di, dj)) && can_move(grid, (ni, nj - 1), (di, dj))
+                }
+                'O' | '[' | ']' => can_move(grid, (ni, nj), (di, dj)),
+                _ => true,
+            }
+        }
+        fn do_move(grid: &mut [Vec<char>], (i, j): (usize, usize), (di, dj): (isize, isize)) {
+            let (ni, nj) = ((i as isize + di) as usize, (j as isize + dj) as usize);
+            match grid[ni][nj] {
+                '[' if di != 0 => {
+                    do_move(grid, (ni, nj), (di, dj));
+                    do_move(grid, (ni, nj + 1), (di, dj));
+                }
+                ']' if di != 0 => {
+                    do_move(grid, (ni, nj), (di, dj));
+                    do_move(grid, (ni, nj - 1), (di, dj));
+                }
+                'O' | '[' | ']' => do_move(grid, (ni, nj), (di, dj)),
+                _ => {}
+            }
+            grid[ni][nj] = grid[i][j];
+            grid[i][j] = '.';
+        }
+        for c in instructions.chars() {
+            let direction = match c {
+                '^' => (-1, 0),
+                '>' => (0, 1),
+                'v' => (1, 0),
+                '<' => (0, -1),
+                _ => continue,
+            };
+            if can_move(&grid, (i, j), direction) {
+                do_move(&mut grid, (i, j), direction);
+                i = (i as isize + direction.0) as usize;
+                j = (j as isize + direction.1) as usize;
+            }
+        }
+        grid.iter()
+            .enumerate()
+            .flat_map(|(i, row)| {
+                row.iter()
+                    .enumerate()
+                    .filter(|(_, &c)| c == 'O' || c == '[')
+                    .map(move |(j, _)| 100 * i + j)
+            })
+            .sum()
+    }
+
+    #[test]
+    fn matches_reference_on_random_maps() {
+        // xorshift64, so the maps are the same on every run
+        let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+        let mut random = |n: usize| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state % n as u64) as usize
+        };
+        for _ in 0..300 {
+            let (rows, cols) = (3 + random(8), 3 + random(8));
+            let mut cells: Vec<Vec<char>> = (0..rows)
+                .map(|_| {
+                    (0..cols)
+                        .map(|_| match random(20) {
+                            0..=11 => '.',
+                            12..=16 => 'O',
+                            _ => '#',
+                        })
+                        .collect()
+                })
+                .collect();
+            cells[random(rows)][random(cols)] = '@';
+            let border = "#".repeat(cols + 2);
+            let map = std::iter::once(border.clone())
+                .chain(
+                    cells
+                        .iter()
+                        .map(|row| format!("#{}#", row.iter().collect::<String>())),
+                )
+                .chain(std::iter::once(border))
+                .join("\n");
+            let instructions: String = (0..60).map(|_| ['^', '>', 'v', '<'][random(4)]).collect();
+            let input = format!("{}\n\n{}", map, instructions);
+            for scale in [1, 2] {
+                assert_eq!(
+                    run(&input, scale as isize).gps(),
+                    reference_gps(&map, &instructions, scale),
+                    "scale {} on\n{}",
+                    scale,
+                    input
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,59 @@
+mod day01;
+mod day02;
+mod day03;
+mod day04;
+mod day05;
+mod day06;
+mod day07;
+mod day08;
+mod day09;
+mod day10;
+mod day11;
+mod day12;
+mod day13;
+mod day14;
+mod day15;
+mod day16;
+
+use day01::day01;
+use day02::day02;
+use day03::day03;
+use day04::day04;
+use day05::day05;
+use day06::day06;
+use day07::day07;
+use day08::day08;
+use day09::day09;
+use day10::day10;
+use day11::day11;
+use day12::day12;
+use day13::day13;
+use day14::day14;
+use day15::day15;
+use day16::day16;
+
+use crate::Solver;
+
+/// The solver for the given day of the 2024 event, if it has been solved.
+pub fn solver(day: u8) -> Option<Solver> {
+    let solver: Solver = match day {
+        1 => day01,
+        2 => day02,
+        3 => day03,
+        4 => day04,
+        5 => day05,
+        6 => day06,
+        7 => day07,
+        8 => day08,
+        9 => day09,
+        10 => day10,
+        11 => day11,
+        12 => day12,
+        13 => day13,
+        14 => |input_path, _| day14(input_path),
+        15 => day15,
+        16 => |input_path, _| day16(input_path),
+        _ => return None,
+    };
+    Some(solver)
+}
@@ -0,0 +1,44 @@
+use std::fs::read_to_string;
+use std::path::Path;
+
+use anyhow::Result;
+
+use crate::options::Options;
+
+type Input = Vec<String>;
+
+fn parse(contents: &str) -> Result<Input> {
+    Ok(contents.split("\n").map(|line| line.to_string()).collect())
+}
+
+fn part1(_input: &Input) -> Result<usize> {
+    Ok(0)
+}
+
+fn part2(_input: &Input) -> Result<usize> {
+    Ok(0)
+}
+
+pub fn day{{day}}(input_path: &Path, _options: &Options) -> Result<(String, String)> {
+    let contents: String = read_to_string(input_path).expect("Error reading file");
+    let input = parse(&contents)?;
+    let p1 = part1(&input)?;
+    let p2 = part2(&input)?;
+    Ok((p1.to_string(), p2.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "";
+
+    #[test]
+    #[ignore = "fill in the puzzle's example input and answers"]
+    fn example() -> Result<()> {
+        let input = parse(EXAMPLE)?;
+        assert_eq!(part1(&input)?, 0);
+        assert_eq!(part2(&input)?, 0);
+        Ok(())
+    }
+}
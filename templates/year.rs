@@ -0,0 +1,9 @@
+use crate::Solver;
+
+/// The solver for the given day of the {{year}} event, if it has been solved.
+pub fn solver(day: u8) -> Option<Solver> {
+    let solver: Solver = match day {
+        _ => return None,
+    };
+    Some(solver)
+}